@@ -6,8 +6,12 @@ use anyhow::Error;
 use bitflags::bitflags;
 use log::{warn};
 use ntrim_tools::tokiort;
+use tokio::sync::broadcast;
 use crate::client::qsecurity::QSecurity;
 use crate::client::trpc::TrpcClient;
+use crate::events::BotEvent;
+use crate::service::cache::MetadataCache;
+use crate::service::highway::HighwayCache;
 use crate::servlet::olpush::OlPushServlet;
 use crate::servlet::register::RegisterProxyServlet;
 use crate::session::SsoSession;
@@ -29,6 +33,12 @@ pub struct Bot {
     pub client: Arc<TrpcClient>,
     /// Bot status.
     pub status: AtomicU32,
+    /// Event bus.
+    events: broadcast::Sender<BotEvent>,
+    /// Highway sig session, per bot.
+    pub(crate) highway: HighwayCache,
+    /// Group, member and friend metadata, per bot.
+    pub(crate) metadata: MetadataCache,
 }
 
 impl Bot {
@@ -41,6 +51,10 @@ impl Bot {
         let bot = Arc::new(Self {
             client,
             status: AtomicU32::new(BotStatus::Offline.bits()),
+            events: broadcast::channel(option_env!("EVENT_CHANNEL_SIZE")
+                .map_or(1024, |v| v.parse::<usize>().unwrap_or(1024))).0,
+            highway: HighwayCache::default(),
+            metadata: MetadataCache::default(),
         });
         RegisterProxyServlet::initialize(&bot).await;
         OlPushServlet::initialize(&bot).await;
@@ -71,6 +85,16 @@ impl Bot {
         self.client.set_lost().await;
     }
 
+    /// 订阅推送事件，订阅之前产生的事件不会收到
+    pub fn subscribe(&self) -> broadcast::Receiver<BotEvent> {
+        self.events.subscribe()
+    }
+
+    /// 派发事件，没有订阅者时直接丢弃
+    pub(crate) fn emit(&self, event: BotEvent) {
+        let _ = self.events.send(event);
+    }
+
     pub async fn is_online(&self) -> bool {
         self.client.is_connected().await &&
             BotStatus::from_bits(self.status.load(SeqCst)).unwrap().contains(BotStatus::Online)
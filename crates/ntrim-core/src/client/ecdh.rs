@@ -0,0 +1,69 @@
+use std::sync::Arc;
+use std::time::Duration;
+use anyhow::Error;
+use log::{info, warn};
+use serde::Deserialize;
+use ntrim_tools::crypto::ecdh::{Ecdh, EcdhCurve};
+use crate::client::trpc::TrpcClient;
+
+#[derive(Debug, Deserialize)]
+struct RotateKeyResponse {
+    #[serde(rename = "PubKeyMeta")]
+    pub_key_meta: PubKeyMeta,
+}
+
+#[derive(Debug, Deserialize)]
+struct PubKeyMeta {
+    #[serde(rename = "KeyVer")]
+    key_ver: u16,
+    #[serde(rename = "PubKey")]
+    pub_key: String,
+}
+
+/// 从keyrotate获取服务器当前的P-256公钥，返回(版本, 公钥)
+///
+/// 官方客户端会用内置公钥校验下发公钥的签名，这里没有校验，
+/// 因此只在显式开启`rotate_key`时使用
+pub async fn fetch_server_public_key(uin: u64) -> Result<(u16, Vec<u8>), Error> {
+    let url = format!("https://keyrotate.qq.com/rotate_key?cipher_suite_ver=305&uin={}", uin);
+    let rsp = reqwest::Client::builder()
+        .timeout(Duration::from_secs(10))
+        .build()?
+        .get(url)
+        .send().await?
+        .json::<RotateKeyResponse>().await?;
+    let pub_key = hex::decode(&rsp.pub_key_meta.pub_key)?;
+    Ok((rsp.pub_key_meta.key_ver, pub_key))
+}
+
+impl TrpcClient {
+    /// 为新的登录重新生成ECDH密钥对
+    ///
+    /// 默认使用曲线内置的服务器公钥；`rotate_key`为真且曲线为P-256时先尝试获取轮换后的公钥，
+    /// 获取失败时回退到内置公钥
+    pub async fn renew_ecdh(self: &Arc<Self>, curve: EcdhCurve, rotate_key: bool) {
+        let ecdh = match curve {
+            EcdhCurve::Prime256v1 if rotate_key => {
+                warn!("Ecdh key rotation is enabled, the rotated server public key is not signature-checked");
+                let uin = self.session.read().await.uin;
+                match fetch_server_public_key(uin).await {
+                    Ok((version, key)) => Ecdh::new(curve, &key, version).unwrap_or_else(|e| {
+                        warn!("Invalid server public key: {}, fallback to builtin", e);
+                        Ecdh::with_builtin_key(curve)
+                    }),
+                    Err(e) => {
+                        warn!("Failed to fetch server public key: {}, fallback to builtin", e);
+                        Ecdh::with_builtin_key(curve)
+                    }
+                }
+            }
+            EcdhCurve::Secp192k1 if rotate_key => {
+                warn!("Key rotation only serves p256 keys, using the builtin secp192k1 key");
+                Ecdh::with_builtin_key(curve)
+            }
+            _ => Ecdh::with_builtin_key(curve),
+        };
+        info!("Using ecdh {:?} with server public key version: {}", ecdh.curve, ecdh.server_public_key_version);
+        self.session.write().await.ecdh = ecdh;
+    }
+}
@@ -3,6 +3,7 @@ pub mod packet;
 pub mod qsecurity;
 pub mod dispatcher;
 pub mod unipacket;
+pub mod ecdh;
 
 pub(crate) mod codec;
 pub(crate) mod tcp;
@@ -4,7 +4,7 @@ use tokio::sync::mpsc::Sender;
 use tokio::sync::oneshot;
 use crate::client::codec;
 use crate::client::packet::{FromServiceMsg, ToServiceMsg};
-use crate::client::packet::packet::CommandType::{ExchangeSig, ExchangeSt, Register, Service};
+use crate::client::packet::packet::CommandType::{ExchangeSig, ExchangeSt, Login, Register, Service};
 use crate::client::packet::packet::UniPacket;
 use crate::client::trpc::TrpcClient;
 use crate::commands;
@@ -78,6 +78,9 @@ impl TrpcClient {
             ExchangeSig => {
                 // nothing
             }
+            Login => {
+                // nothing
+            }
             _ => {
                 error!("Invalid command type: {:?}", msg.uni_packet.command_type);
             }
@@ -0,0 +1,34 @@
+use log::info;
+use prost::Message;
+use ntrim_macros::command;
+use crate::{oidb_request, oidb_result, pb};
+use crate::pb::oidb::TrpcOidbResponse;
+use crate::pb::oidb::cmd0x126b::{ * };
+
+struct DeleteFriendBuilder;
+
+#[command("OidbSvcTrpcTcp.0x126b_0", "delete_friend", Protobuf, Service)]
+impl DeleteFriendBuilder {
+    async fn generate(bot: &Arc<Bot>, uid: String, block: bool) -> Option<Vec<u8>> {
+        oidb_request!(0x126b, 0, DeleteFriendReq {
+            body: DeleteFriendBody {
+                target_uid: uid,
+                field2: DeleteFriendField2 {
+                    field1: Some(130),
+                    field2: Some(109),
+                    field3: Some(DeleteFriendField3 {
+                        field1: Some(8),
+                        field2: Some(8),
+                        field3: Some(50),
+                    }),
+                },
+                block: Some(block),
+                field4: Some(false),
+            },
+        }.encode_to_vec())
+    }
+
+    async fn parse(bot: &Arc<Bot>, data: Vec<u8>) -> Option<TrpcOidbResponse> {
+        oidb_result!(0x126b, 0, data.as_slice())
+    }
+}
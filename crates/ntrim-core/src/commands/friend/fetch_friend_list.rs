@@ -0,0 +1,43 @@
+use log::info;
+use prost::Message;
+use ntrim_macros::command;
+use crate::{oidb_request, oidb_response, pb};
+use crate::pb::oidb::cmd0xfd4::{ * };
+
+struct FetchFriendListBuilder;
+
+#[command("OidbSvcTrpcTcp.0xfd4_1", "fetch_friend_list", Protobuf, Service)]
+impl FetchFriendListBuilder {
+    async fn generate(bot: &Arc<Bot>, next_uin: Option<u64>) -> Option<Vec<u8>> {
+        oidb_request!(0xfd4, 1, FetchFriendListReq {
+            friend_count: 300,
+            field4: Some(0),
+            next_uin: next_uin.map(|uin| NextUin { uin }),
+            field6: Some(1),
+            field7: Some(i32::MAX as u32),
+            body: vec![
+                PropertyRequest {
+                    r#type: 1,
+                    keys: PropertyKeys { keys: vec![103, 102, 20002, 27394] },
+                },
+                PropertyRequest {
+                    r#type: 4,
+                    keys: PropertyKeys { keys: vec![100, 101, 102] },
+                },
+            ],
+            field10002: vec![13578, 13579, 13573, 13572, 13568],
+            field10003: Some(4051),
+        }.encode_to_vec())
+    }
+
+    async fn parse(bot: &Arc<Bot>, data: Vec<u8>) -> Option<FetchFriendListRsp> {
+        let response = oidb_response!(0xfd4, 1, data.as_slice())?;
+        match FetchFriendListRsp::decode(response.as_slice()) {
+            Ok(rsp) => Some(rsp),
+            Err(e) => {
+                error!("Failed to decode FetchFriendListRsp: {:?}, data: {}", e, hex::encode(&response));
+                None
+            }
+        }
+    }
+}
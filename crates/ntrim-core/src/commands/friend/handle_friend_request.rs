@@ -0,0 +1,22 @@
+use log::info;
+use prost::Message;
+use ntrim_macros::command;
+use crate::{oidb_request, oidb_result, pb};
+use crate::pb::oidb::TrpcOidbResponse;
+use crate::pb::oidb::cmd0xb5d::HandleFriendRequestReq;
+
+struct HandleFriendRequestBuilder;
+
+#[command("OidbSvcTrpcTcp.0xb5d_44", "handle_friend_request", Protobuf, Service)]
+impl HandleFriendRequestBuilder {
+    async fn generate(bot: &Arc<Bot>, uid: String, approve: bool) -> Option<Vec<u8>> {
+        oidb_request!(0xb5d, 44, HandleFriendRequestReq {
+            accept: if approve { 3 } else { 5 },
+            target_uid: uid,
+        }.encode_to_vec())
+    }
+
+    async fn parse(bot: &Arc<Bot>, data: Vec<u8>) -> Option<TrpcOidbResponse> {
+        oidb_result!(0xb5d, 44, data.as_slice())
+    }
+}
@@ -0,0 +1,6 @@
+pub mod fetch_friend_list;
+pub mod handle_friend_request;
+pub mod delete_friend;
+pub mod set_friend_remark;
+pub mod request_file_download;
+pub mod send_poke;
@@ -0,0 +1,40 @@
+use log::info;
+use prost::Message;
+use ntrim_macros::command;
+use crate::{oidb_request, oidb_response, pb};
+use crate::pb::oidb::cmd0xe37::{ * };
+
+struct RequestPrivateFileDownloadBuilder;
+
+#[command("OidbSvcTrpcTcp.0xe37_1200", "request_private_file_download", Protobuf, Service)]
+impl RequestPrivateFileDownloadBuilder {
+    async fn generate(bot: &Arc<Bot>, file_uuid: String, file_hash: String) -> Option<Vec<u8>> {
+        let receiver_uid = bot.client.session.read().await.uid.clone();
+        oidb_request!(0xe37, 1200, DownloadOfflineFileReq {
+            sub_command: Some(1200),
+            field2: Some(1),
+            body: DownloadOfflineFileBody {
+                receiver_uid,
+                file_uuid,
+                r#type: Some(2),
+                file_hash: Some(file_hash),
+                t2: Some(0),
+            },
+            field101: Some(3),
+            field102: Some(103),
+            field200: Some(1),
+            field99999: Some(vec![0xc0, 0x85, 0x2c, 0x01]),
+        }.encode_to_vec())
+    }
+
+    async fn parse(bot: &Arc<Bot>, data: Vec<u8>) -> Option<DownloadOfflineFileRsp> {
+        let response = oidb_response!(0xe37, 1200, data.as_slice())?;
+        match DownloadOfflineFileRsp::decode(response.as_slice()) {
+            Ok(rsp) => Some(rsp),
+            Err(e) => {
+                error!("Failed to decode DownloadOfflineFileRsp: {:?}, data: {}", e, hex::encode(&response));
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use log::info;
+use prost::Message;
+use ntrim_macros::command;
+use crate::{oidb_request, oidb_result, pb};
+use crate::pb::oidb::TrpcOidbResponse;
+use crate::pb::oidb::cmd0xed3::PokeReq;
+
+struct SendPokeBuilder;
+
+#[command("OidbSvcTrpcTcp.0xed3_1", "send_poke", Protobuf, Service)]
+impl SendPokeBuilder {
+    // `group_id`为空时戳好友
+    async fn generate(bot: &Arc<Bot>, target_uin: u64, group_id: Option<u64>) -> Option<Vec<u8>> {
+        oidb_request!(0xed3, 1, PokeReq {
+            target_uin,
+            group_id,
+            friend_uin: match group_id {
+                Some(_) => None,
+                None => Some(target_uin),
+            },
+            ext: Some(0),
+        }.encode_to_vec())
+    }
+
+    async fn parse(bot: &Arc<Bot>, data: Vec<u8>) -> Option<TrpcOidbResponse> {
+        oidb_result!(0xed3, 1, data.as_slice())
+    }
+}
@@ -0,0 +1,22 @@
+use log::info;
+use prost::Message;
+use ntrim_macros::command;
+use crate::{oidb_request, oidb_result, pb};
+use crate::pb::oidb::TrpcOidbResponse;
+use crate::pb::oidb::cmd0xb6e::SetFriendRemarkReq;
+
+struct SetFriendRemarkBuilder;
+
+#[command("OidbSvcTrpcTcp.0xb6e_2", "set_friend_remark", Protobuf, Service)]
+impl SetFriendRemarkBuilder {
+    async fn generate(bot: &Arc<Bot>, uid: String, remark: String) -> Option<Vec<u8>> {
+        oidb_request!(0xb6e, 2, SetFriendRemarkReq {
+            target_uid: uid,
+            remark,
+        }.encode_to_vec())
+    }
+
+    async fn parse(bot: &Arc<Bot>, data: Vec<u8>) -> Option<TrpcOidbResponse> {
+        oidb_result!(0xb6e, 2, data.as_slice())
+    }
+}
@@ -0,0 +1,26 @@
+use log::info;
+use prost::Message;
+use ntrim_macros::command;
+use crate::{oidb_request, oidb_result, pb};
+use crate::pb::oidb::TrpcOidbResponse;
+use crate::pb::oidb::cmd0x9082::ReactionReq;
+
+struct AddGroupReactionBuilder;
+
+#[command("OidbSvcTrpcTcp.0x9082_1", "add_group_reaction", Protobuf, Service)]
+impl AddGroupReactionBuilder {
+    async fn generate(bot: &Arc<Bot>, group_id: u64, msg_seq: u32, code: String, reaction_type: u32) -> Option<Vec<u8>> {
+        oidb_request!(0x9082, 1, ReactionReq {
+            group_id,
+            msg_seq,
+            code,
+            r#type: reaction_type,
+            field6: Some(false),
+            field7: Some(false),
+        }.encode_to_vec())
+    }
+
+    async fn parse(bot: &Arc<Bot>, data: Vec<u8>) -> Option<TrpcOidbResponse> {
+        oidb_result!(0x9082, 1, data.as_slice())
+    }
+}
@@ -0,0 +1,76 @@
+use log::info;
+use prost::Message;
+use ntrim_macros::command;
+use crate::{oidb_request, oidb_response, pb};
+use crate::pb::oidb::cmd0xfe5::{ * };
+
+struct FetchGroupListBuilder;
+
+#[command("OidbSvcTrpcTcp.0xfe5_2", "fetch_group_list", Protobuf, Service)]
+impl FetchGroupListBuilder {
+    async fn generate(bot: &Arc<Bot>) -> Option<Vec<u8>> {
+        oidb_request!(0xfe5, 2, FetchGroupListReq {
+            config: GroupListConfig {
+                info: GroupInfoFilter {
+                    owner: Some(true),
+                    field2: Some(true),
+                    max_member_count: Some(true),
+                    member_count: Some(true),
+                    group_name: Some(true),
+                    field8: Some(true),
+                    field9: Some(true),
+                    field10: Some(true),
+                    field11: Some(true),
+                    field12: Some(true),
+                    field13: Some(true),
+                    field14: Some(true),
+                    field15: Some(true),
+                    field16: Some(true),
+                    field17: Some(true),
+                    field18: Some(true),
+                    question: Some(true),
+                    field20: Some(true),
+                    field22: Some(true),
+                    field23: Some(true),
+                    field24: Some(true),
+                    field25: Some(true),
+                    field26: Some(true),
+                    field27: Some(true),
+                    field28: Some(true),
+                    field29: Some(true),
+                    field30: Some(true),
+                    field31: Some(true),
+                    field32: Some(true),
+                    field5001: Some(true),
+                    field5002: Some(true),
+                    field5003: Some(true),
+                },
+                config2: GroupListConfig2 {
+                    field1: Some(true),
+                    field2: Some(true),
+                    field3: Some(true),
+                    field4: Some(true),
+                    field5: Some(true),
+                    field6: Some(true),
+                    field7: Some(true),
+                    field8: Some(true),
+                },
+                config3: GroupListConfig3 {
+                    field5: Some(true),
+                    field6: Some(true),
+                },
+            },
+        }.encode_to_vec())
+    }
+
+    async fn parse(bot: &Arc<Bot>, data: Vec<u8>) -> Option<FetchGroupListRsp> {
+        let response = oidb_response!(0xfe5, 2, data.as_slice())?;
+        match FetchGroupListRsp::decode(response.as_slice()) {
+            Ok(rsp) => Some(rsp),
+            Err(e) => {
+                error!("Failed to decode FetchGroupListRsp: {:?}, data: {}", e, hex::encode(&response));
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use log::info;
+use prost::Message;
+use ntrim_macros::command;
+use crate::{oidb_request, oidb_response, pb};
+use crate::pb::oidb::cmd0x10c0::{ * };
+
+struct FetchGroupRequestsBuilder;
+
+#[command("OidbSvcTrpcTcp.0x10c0_1", "fetch_group_requests", Protobuf, Service)]
+impl FetchGroupRequestsBuilder {
+    async fn generate(bot: &Arc<Bot>, count: u32) -> Option<Vec<u8>> {
+        oidb_request!(0x10c0, 1, FetchGroupRequestsReq {
+            count,
+            field2: Some(0),
+        }.encode_to_vec())
+    }
+
+    async fn parse(bot: &Arc<Bot>, data: Vec<u8>) -> Option<FetchGroupRequestsRsp> {
+        let response = oidb_response!(0x10c0, 1, data.as_slice())?;
+        match FetchGroupRequestsRsp::decode(response.as_slice()) {
+            Ok(rsp) => Some(rsp),
+            Err(e) => {
+                error!("Failed to decode FetchGroupRequestsRsp: {:?}, data: {}", e, hex::encode(&response));
+                None
+            }
+        }
+    }
+}
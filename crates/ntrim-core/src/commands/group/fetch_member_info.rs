@@ -0,0 +1,32 @@
+use log::info;
+use prost::Message;
+use ntrim_macros::command;
+use crate::{oidb_request, oidb_response, pb};
+use crate::commands::group::member_info_filter;
+use crate::pb::oidb::cmd0xfe7::{ * };
+
+struct FetchGroupMemberInfoBuilder;
+
+#[command("OidbSvcTrpcTcp.0xfe7_4", "fetch_group_member_info", Protobuf, Service)]
+impl FetchGroupMemberInfoBuilder {
+    async fn generate(bot: &Arc<Bot>, group_id: u64, uid: String) -> Option<Vec<u8>> {
+        oidb_request!(0xfe7, 4, FetchMemberInfoReq {
+            group_id,
+            field2: 2,
+            field3: 0,
+            filter: member_info_filter(),
+            key: MemberKey { uid },
+        }.encode_to_vec())
+    }
+
+    async fn parse(bot: &Arc<Bot>, data: Vec<u8>) -> Option<FetchMemberInfoRsp> {
+        let response = oidb_response!(0xfe7, 4, data.as_slice())?;
+        match FetchMemberInfoRsp::decode(response.as_slice()) {
+            Ok(rsp) => Some(rsp),
+            Err(e) => {
+                error!("Failed to decode FetchMemberInfoRsp: {:?}, data: {}", e, hex::encode(&response));
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use log::info;
+use prost::Message;
+use ntrim_macros::command;
+use crate::{oidb_request, oidb_response, pb};
+use crate::commands::group::member_info_filter;
+use crate::pb::oidb::cmd0xfe7::{ * };
+
+struct FetchGroupMemberListBuilder;
+
+#[command("OidbSvcTrpcTcp.0xfe7_3", "fetch_group_member_list", Protobuf, Service)]
+impl FetchGroupMemberListBuilder {
+    async fn generate(bot: &Arc<Bot>, group_id: u64, token: Option<String>) -> Option<Vec<u8>> {
+        oidb_request!(0xfe7, 3, FetchMemberListReq {
+            group_id,
+            field2: 5,
+            field3: 2,
+            filter: member_info_filter(),
+            token,
+        }.encode_to_vec())
+    }
+
+    async fn parse(bot: &Arc<Bot>, data: Vec<u8>) -> Option<FetchMemberListRsp> {
+        let response = oidb_response!(0xfe7, 3, data.as_slice())?;
+        match FetchMemberListRsp::decode(response.as_slice()) {
+            Ok(rsp) => Some(rsp),
+            Err(e) => {
+                error!("Failed to decode FetchMemberListRsp: {:?}, data: {}", e, hex::encode(&response));
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,28 @@
+use log::info;
+use prost::Message;
+use ntrim_macros::command;
+use crate::{oidb_request, oidb_result, pb};
+use crate::pb::oidb::TrpcOidbResponse;
+use crate::pb::oidb::cmd0x10c8::{ * };
+
+struct HandleGroupRequestBuilder;
+
+#[command("OidbSvcTrpcTcp.0x10c8_1", "handle_group_request", Protobuf, Service)]
+impl HandleGroupRequestBuilder {
+    async fn generate(bot: &Arc<Bot>, group_id: u64, seq: u64, event_type: u32, approve: bool, reason: String, block: bool) -> Option<Vec<u8>> {
+        oidb_request!(0x10c8, 1, HandleGroupRequestReq {
+            accept: if approve { 1 } else { 2 },
+            body: HandleGroupRequestBody {
+                seq,
+                event_type,
+                group_id,
+                reason: Some(reason),
+                block: Some(block),
+            },
+        }.encode_to_vec())
+    }
+
+    async fn parse(bot: &Arc<Bot>, data: Vec<u8>) -> Option<TrpcOidbResponse> {
+        oidb_result!(0x10c8, 1, data.as_slice())
+    }
+}
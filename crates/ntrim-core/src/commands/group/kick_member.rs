@@ -0,0 +1,24 @@
+use log::info;
+use prost::Message;
+use ntrim_macros::command;
+use crate::{oidb_request, oidb_result, pb};
+use crate::pb::oidb::TrpcOidbResponse;
+use crate::pb::oidb::cmd0x8a0::KickMemberReq;
+
+struct KickGroupMemberBuilder;
+
+#[command("OidbSvcTrpcTcp.0x8a0_1", "kick_group_member", Protobuf, Service)]
+impl KickGroupMemberBuilder {
+    async fn generate(bot: &Arc<Bot>, group_id: u64, uid: String, reject_add_request: bool, reason: String) -> Option<Vec<u8>> {
+        oidb_request!(0x8a0, 1, KickMemberReq {
+            group_id,
+            target_uid: uid,
+            reject_add_request,
+            reason: Some(reason),
+        }.encode_to_vec())
+    }
+
+    async fn parse(bot: &Arc<Bot>, data: Vec<u8>) -> Option<TrpcOidbResponse> {
+        oidb_result!(0x8a0, 1, data.as_slice())
+    }
+}
@@ -0,0 +1,33 @@
+pub mod fetch_member_list;
+pub mod fetch_member_info;
+pub mod fetch_group_list;
+pub mod kick_member;
+pub mod mute_member;
+pub mod mute_whole_group;
+pub mod set_admin;
+pub mod rename_group;
+pub mod set_member_card;
+pub mod set_special_title;
+pub mod fetch_group_requests;
+pub mod handle_group_request;
+pub mod set_essence;
+pub mod remove_essence;
+pub mod add_reaction;
+pub mod remove_reaction;
+
+use crate::pb::oidb::cmd0xfe7::MemberInfoFilter;
+
+/// 群成员信息需要拉取的字段
+pub(crate) fn member_info_filter() -> MemberInfoFilter {
+    MemberInfoFilter {
+        nickname: Some(true),
+        card: Some(true),
+        level: Some(true),
+        special_title: Some(true),
+        join_time: Some(true),
+        last_speak_time: Some(true),
+        shut_up_time: Some(true),
+        role: Some(true),
+        ..Default::default()
+    }
+}
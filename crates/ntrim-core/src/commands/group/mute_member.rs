@@ -0,0 +1,26 @@
+use log::info;
+use prost::Message;
+use ntrim_macros::command;
+use crate::{oidb_request, oidb_result, pb};
+use crate::pb::oidb::TrpcOidbResponse;
+use crate::pb::oidb::cmd0x1253::{ * };
+
+struct MuteGroupMemberBuilder;
+
+#[command("OidbSvcTrpcTcp.0x1253_1", "mute_group_member", Protobuf, Service)]
+impl MuteGroupMemberBuilder {
+    async fn generate(bot: &Arc<Bot>, group_id: u64, uid: String, duration: u32) -> Option<Vec<u8>> {
+        oidb_request!(0x1253, 1, MuteMemberReq {
+            group_id,
+            r#type: Some(1),
+            body: MuteMemberBody {
+                target_uid: uid,
+                duration,
+            },
+        }.encode_to_vec())
+    }
+
+    async fn parse(bot: &Arc<Bot>, data: Vec<u8>) -> Option<TrpcOidbResponse> {
+        oidb_result!(0x1253, 1, data.as_slice())
+    }
+}
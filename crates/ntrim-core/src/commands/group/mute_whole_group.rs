@@ -0,0 +1,25 @@
+use log::info;
+use prost::Message;
+use ntrim_macros::command;
+use crate::{oidb_request, oidb_result, pb};
+use crate::pb::oidb::TrpcOidbResponse;
+use crate::pb::oidb::cmd0x89a::{ * };
+
+struct MuteWholeGroupBuilder;
+
+#[command("OidbSvcTrpcTcp.0x89a_0", "mute_whole_group", Protobuf, Service)]
+impl MuteWholeGroupBuilder {
+    async fn generate(bot: &Arc<Bot>, group_id: u64, enable: bool) -> Option<Vec<u8>> {
+        oidb_request!(0x89a, 0, GroupSettingReq {
+            group_id,
+            settings: GroupSettings {
+                shut_up_time: Some(if enable { u32::MAX } else { 0 }),
+                ..Default::default()
+            },
+        }.encode_to_vec())
+    }
+
+    async fn parse(bot: &Arc<Bot>, data: Vec<u8>) -> Option<TrpcOidbResponse> {
+        oidb_result!(0x89a, 0, data.as_slice())
+    }
+}
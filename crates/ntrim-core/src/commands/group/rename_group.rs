@@ -0,0 +1,25 @@
+use log::info;
+use prost::Message;
+use ntrim_macros::command;
+use crate::{oidb_request, oidb_result, pb};
+use crate::pb::oidb::TrpcOidbResponse;
+use crate::pb::oidb::cmd0x89a::{ * };
+
+struct RenameGroupBuilder;
+
+#[command("OidbSvcTrpcTcp.0x89a_15", "rename_group", Protobuf, Service)]
+impl RenameGroupBuilder {
+    async fn generate(bot: &Arc<Bot>, group_id: u64, group_name: String) -> Option<Vec<u8>> {
+        oidb_request!(0x89a, 15, GroupSettingReq {
+            group_id,
+            settings: GroupSettings {
+                group_name: Some(group_name),
+                ..Default::default()
+            },
+        }.encode_to_vec())
+    }
+
+    async fn parse(bot: &Arc<Bot>, data: Vec<u8>) -> Option<TrpcOidbResponse> {
+        oidb_result!(0x89a, 15, data.as_slice())
+    }
+}
@@ -0,0 +1,23 @@
+use log::info;
+use prost::Message;
+use ntrim_macros::command;
+use crate::{oidb_request, oidb_result, pb};
+use crate::pb::oidb::TrpcOidbResponse;
+use crate::pb::oidb::cmd0x1096::SetAdminReq;
+
+struct SetGroupAdminBuilder;
+
+#[command("OidbSvcTrpcTcp.0x1096_1", "set_group_admin", Protobuf, Service)]
+impl SetGroupAdminBuilder {
+    async fn generate(bot: &Arc<Bot>, group_id: u64, uid: String, is_admin: bool) -> Option<Vec<u8>> {
+        oidb_request!(0x1096, 1, SetAdminReq {
+            group_id,
+            uid,
+            is_admin,
+        }.encode_to_vec())
+    }
+
+    async fn parse(bot: &Arc<Bot>, data: Vec<u8>) -> Option<TrpcOidbResponse> {
+        oidb_result!(0x1096, 1, data.as_slice())
+    }
+}
@@ -0,0 +1,23 @@
+use log::info;
+use prost::Message;
+use ntrim_macros::command;
+use crate::{oidb_request, oidb_result, pb};
+use crate::pb::oidb::TrpcOidbResponse;
+use crate::pb::oidb::cmd0xeac::EssenceReq;
+
+struct SetEssenceMessageBuilder;
+
+#[command("OidbSvcTrpcTcp.0xeac_1", "set_essence_message", Protobuf, Service)]
+impl SetEssenceMessageBuilder {
+    async fn generate(bot: &Arc<Bot>, group_id: u64, msg_seq: u32, msg_random: u32) -> Option<Vec<u8>> {
+        oidb_request!(0xeac, 1, EssenceReq {
+            group_id,
+            msg_seq,
+            msg_random,
+        }.encode_to_vec())
+    }
+
+    async fn parse(bot: &Arc<Bot>, data: Vec<u8>) -> Option<TrpcOidbResponse> {
+        oidb_result!(0xeac, 1, data.as_slice())
+    }
+}
@@ -0,0 +1,26 @@
+use log::info;
+use prost::Message;
+use ntrim_macros::command;
+use crate::{oidb_request, oidb_result, pb};
+use crate::pb::oidb::TrpcOidbResponse;
+use crate::pb::oidb::cmd0x8fc::{ * };
+
+struct SetGroupMemberCardBuilder;
+
+#[command("OidbSvcTrpcTcp.0x8fc_3", "set_group_member_card", Protobuf, Service)]
+impl SetGroupMemberCardBuilder {
+    async fn generate(bot: &Arc<Bot>, group_id: u64, uid: String, card: String) -> Option<Vec<u8>> {
+        oidb_request!(0x8fc, 3, SetMemberReq {
+            group_id,
+            body: SetMemberBody {
+                target_uid: uid,
+                card: Some(card),
+                ..Default::default()
+            },
+        }.encode_to_vec())
+    }
+
+    async fn parse(bot: &Arc<Bot>, data: Vec<u8>) -> Option<TrpcOidbResponse> {
+        oidb_result!(0x8fc, 3, data.as_slice())
+    }
+}
@@ -0,0 +1,28 @@
+use log::info;
+use prost::Message;
+use ntrim_macros::command;
+use crate::{oidb_request, oidb_result, pb};
+use crate::pb::oidb::TrpcOidbResponse;
+use crate::pb::oidb::cmd0x8fc::{ * };
+
+struct SetGroupSpecialTitleBuilder;
+
+#[command("OidbSvcTrpcTcp.0x8fc_2", "set_group_special_title", Protobuf, Service)]
+impl SetGroupSpecialTitleBuilder {
+    async fn generate(bot: &Arc<Bot>, group_id: u64, uid: String, title: String, expire_time: i32) -> Option<Vec<u8>> {
+        oidb_request!(0x8fc, 2, SetMemberReq {
+            group_id,
+            body: SetMemberBody {
+                target_uid: uid,
+                special_title: Some(title.clone()),
+                special_title_expire_time: Some(expire_time),
+                uin_name: Some(title),
+                ..Default::default()
+            },
+        }.encode_to_vec())
+    }
+
+    async fn parse(bot: &Arc<Bot>, data: Vec<u8>) -> Option<TrpcOidbResponse> {
+        oidb_result!(0x8fc, 2, data.as_slice())
+    }
+}
@@ -0,0 +1,32 @@
+use log::info;
+use prost::Message;
+use ntrim_macros::command;
+use crate::{oidb_request, oidb_response, pb};
+use crate::pb::oidb::cmd0x6d7::{ * };
+
+struct CreateGroupFolderBuilder;
+
+#[command("OidbSvcTrpcTcp.0x6d7_0", "create_group_folder", Protobuf, Service)]
+impl CreateGroupFolderBuilder {
+    async fn generate(bot: &Arc<Bot>, group_id: u64, folder_name: String) -> Option<Vec<u8>> {
+        oidb_request!(0x6d7, 0, GroupFolderReq {
+            create: Some(CreateFolderReq {
+                group_id,
+                parent_folder_id: "/".to_string(),
+                folder_name,
+            }),
+            ..Default::default()
+        }.encode_to_vec())
+    }
+
+    async fn parse(bot: &Arc<Bot>, data: Vec<u8>) -> Option<GroupFolderRsp> {
+        let response = oidb_response!(0x6d7, 0, data.as_slice())?;
+        match GroupFolderRsp::decode(response.as_slice()) {
+            Ok(rsp) => Some(rsp),
+            Err(e) => {
+                error!("Failed to decode GroupFolderRsp(0x6d7_0): {:?}, data: {}", e, hex::encode(&response));
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,32 @@
+use log::info;
+use prost::Message;
+use ntrim_macros::command;
+use crate::{oidb_request, oidb_response, pb};
+use crate::pb::oidb::cmd0x6d6::{ * };
+
+struct DeleteGroupFileBuilder;
+
+#[command("OidbSvcTrpcTcp.0x6d6_3", "delete_group_file", Protobuf, Service)]
+impl DeleteGroupFileBuilder {
+    async fn generate(bot: &Arc<Bot>, group_id: u64, file_id: String) -> Option<Vec<u8>> {
+        oidb_request!(0x6d6, 3, GroupFileReq {
+            delete: Some(DeleteFileReq {
+                group_id,
+                bus_id: Some(102),
+                file_id,
+            }),
+            ..Default::default()
+        }.encode_to_vec())
+    }
+
+    async fn parse(bot: &Arc<Bot>, data: Vec<u8>) -> Option<GroupFileRsp> {
+        let response = oidb_response!(0x6d6, 3, data.as_slice())?;
+        match GroupFileRsp::decode(response.as_slice()) {
+            Ok(rsp) => Some(rsp),
+            Err(e) => {
+                error!("Failed to decode GroupFileRsp(0x6d6_3): {:?}, data: {}", e, hex::encode(&response));
+                None
+            }
+        }
+    }
+}
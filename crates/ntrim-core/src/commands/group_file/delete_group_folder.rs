@@ -0,0 +1,31 @@
+use log::info;
+use prost::Message;
+use ntrim_macros::command;
+use crate::{oidb_request, oidb_response, pb};
+use crate::pb::oidb::cmd0x6d7::{ * };
+
+struct DeleteGroupFolderBuilder;
+
+#[command("OidbSvcTrpcTcp.0x6d7_1", "delete_group_folder", Protobuf, Service)]
+impl DeleteGroupFolderBuilder {
+    async fn generate(bot: &Arc<Bot>, group_id: u64, folder_id: String) -> Option<Vec<u8>> {
+        oidb_request!(0x6d7, 1, GroupFolderReq {
+            delete: Some(DeleteFolderReq {
+                group_id,
+                folder_id,
+            }),
+            ..Default::default()
+        }.encode_to_vec())
+    }
+
+    async fn parse(bot: &Arc<Bot>, data: Vec<u8>) -> Option<GroupFolderRsp> {
+        let response = oidb_response!(0x6d7, 1, data.as_slice())?;
+        match GroupFolderRsp::decode(response.as_slice()) {
+            Ok(rsp) => Some(rsp),
+            Err(e) => {
+                error!("Failed to decode GroupFolderRsp(0x6d7_1): {:?}, data: {}", e, hex::encode(&response));
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use log::info;
+use prost::Message;
+use ntrim_macros::command;
+use crate::{oidb_request, oidb_response, pb};
+use crate::pb::oidb::cmd0x6d8::{ * };
+
+struct FetchGroupFilesBuilder;
+
+#[command("OidbSvcTrpcTcp.0x6d8_1", "fetch_group_files", Protobuf, Service)]
+impl FetchGroupFilesBuilder {
+    async fn generate(bot: &Arc<Bot>, group_id: u64, folder_id: String, start_index: u32, file_count: u32) -> Option<Vec<u8>> {
+        oidb_request!(0x6d8, 1, FetchGroupFilesReq {
+            list: FetchGroupFilesBody {
+                group_id,
+                app_id: Some(7),
+                folder_id,
+                file_count,
+                sort_by: Some(1),
+                start_index,
+                field17: Some(2),
+                field18: Some(0),
+            },
+        }.encode_to_vec())
+    }
+
+    async fn parse(bot: &Arc<Bot>, data: Vec<u8>) -> Option<FetchGroupFilesRsp> {
+        let response = oidb_response!(0x6d8, 1, data.as_slice())?;
+        match FetchGroupFilesRsp::decode(response.as_slice()) {
+            Ok(rsp) => Some(rsp),
+            Err(e) => {
+                error!("Failed to decode FetchGroupFilesRsp(0x6d8_1): {:?}, data: {}", e, hex::encode(&response));
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,9 @@
+pub mod fetch_group_files;
+pub mod create_group_folder;
+pub mod delete_group_folder;
+pub mod rename_group_folder;
+pub mod request_group_file_upload;
+pub mod request_group_file_download;
+pub mod delete_group_file;
+pub mod move_group_file;
+pub mod send_group_file;
@@ -0,0 +1,35 @@
+use log::info;
+use prost::Message;
+use ntrim_macros::command;
+use crate::{oidb_request, oidb_response, pb};
+use crate::pb::oidb::cmd0x6d6::{ * };
+
+struct MoveGroupFileBuilder;
+
+#[command("OidbSvcTrpcTcp.0x6d6_5", "move_group_file", Protobuf, Service)]
+impl MoveGroupFileBuilder {
+    async fn generate(bot: &Arc<Bot>, group_id: u64, file_id: String, parent_folder_id: String, target_folder_id: String) -> Option<Vec<u8>> {
+        oidb_request!(0x6d6, 5, GroupFileReq {
+            r#move: Some(MoveFileReq {
+                group_id,
+                app_id: Some(7),
+                bus_id: Some(102),
+                file_id,
+                parent_folder_id,
+                target_folder_id,
+            }),
+            ..Default::default()
+        }.encode_to_vec())
+    }
+
+    async fn parse(bot: &Arc<Bot>, data: Vec<u8>) -> Option<GroupFileRsp> {
+        let response = oidb_response!(0x6d6, 5, data.as_slice())?;
+        match GroupFileRsp::decode(response.as_slice()) {
+            Ok(rsp) => Some(rsp),
+            Err(e) => {
+                error!("Failed to decode GroupFileRsp(0x6d6_5): {:?}, data: {}", e, hex::encode(&response));
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use log::info;
+use prost::Message;
+use ntrim_macros::command;
+use crate::{oidb_request, oidb_response, pb};
+use crate::pb::oidb::cmd0x6d6::{ * };
+
+struct RequestGroupFileDownloadBuilder;
+
+#[command("OidbSvcTrpcTcp.0x6d6_2", "request_group_file_download", Protobuf, Service)]
+impl RequestGroupFileDownloadBuilder {
+    async fn generate(bot: &Arc<Bot>, group_id: u64, file_id: String) -> Option<Vec<u8>> {
+        oidb_request!(0x6d6, 2, GroupFileReq {
+            download: Some(DownloadFileReq {
+                group_id,
+                app_id: Some(7),
+                bus_id: Some(102),
+                file_id,
+            }),
+            ..Default::default()
+        }.encode_to_vec())
+    }
+
+    async fn parse(bot: &Arc<Bot>, data: Vec<u8>) -> Option<GroupFileRsp> {
+        let response = oidb_response!(0x6d6, 2, data.as_slice())?;
+        match GroupFileRsp::decode(response.as_slice()) {
+            Ok(rsp) => Some(rsp),
+            Err(e) => {
+                error!("Failed to decode GroupFileRsp(0x6d6_2): {:?}, data: {}", e, hex::encode(&response));
+                None
+            }
+        }
+    }
+}
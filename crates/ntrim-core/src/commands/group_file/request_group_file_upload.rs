@@ -0,0 +1,41 @@
+use log::info;
+use prost::Message;
+use ntrim_macros::command;
+use crate::{oidb_request, oidb_response, pb};
+use crate::pb::oidb::cmd0x6d6::{ * };
+
+struct RequestGroupFileUploadBuilder;
+
+#[command("OidbSvcTrpcTcp.0x6d6_0", "request_group_file_upload", Protobuf, Service)]
+impl RequestGroupFileUploadBuilder {
+    async fn generate(bot: &Arc<Bot>, group_id: u64, folder_id: String, file_name: String, file_size: u64, file_sha1: Vec<u8>, file_md5: Vec<u8>) -> Option<Vec<u8>> {
+        oidb_request!(0x6d6, 0, GroupFileReq {
+            upload: Some(UploadFileReq {
+                group_id,
+                app_id: Some(4),
+                bus_id: Some(102),
+                entrance: Some(6),
+                target_folder_id: folder_id,
+                local_directory: Some(format!("/{}", file_name)),
+                file_name,
+                file_size,
+                file_sha1: Some(file_sha1),
+                file_sha3: Some(Vec::new()),
+                file_md5: Some(file_md5),
+                field15: Some(true),
+            }),
+            ..Default::default()
+        }.encode_to_vec())
+    }
+
+    async fn parse(bot: &Arc<Bot>, data: Vec<u8>) -> Option<GroupFileRsp> {
+        let response = oidb_response!(0x6d6, 0, data.as_slice())?;
+        match GroupFileRsp::decode(response.as_slice()) {
+            Ok(rsp) => Some(rsp),
+            Err(e) => {
+                error!("Failed to decode GroupFileRsp(0x6d6_0): {:?}, data: {}", e, hex::encode(&response));
+                None
+            }
+        }
+    }
+}
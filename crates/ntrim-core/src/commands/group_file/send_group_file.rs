@@ -0,0 +1,37 @@
+use log::info;
+use prost::Message;
+use ntrim_macros::command;
+use crate::{oidb_request, oidb_response, pb};
+use crate::pb::oidb::cmd0x6d9::{ * };
+
+struct SendGroupFileBuilder;
+
+#[command("OidbSvcTrpcTcp.0x6d9_4", "send_group_file", Protobuf, Service)]
+impl SendGroupFileBuilder {
+    async fn generate(bot: &Arc<Bot>, group_id: u64, file_id: String) -> Option<Vec<u8>> {
+        oidb_request!(0x6d9, 4, SendGroupFileReq {
+            file: SendGroupFileBody {
+                group_id,
+                r#type: Some(2),
+                info: SendGroupFileInfo {
+                    bus_type: Some(102),
+                    file_id,
+                    random: Some(rand::random::<u32>()),
+                    field4: Some("{}".to_string()),
+                    field5: Some(true),
+                },
+            },
+        }.encode_to_vec())
+    }
+
+    async fn parse(bot: &Arc<Bot>, data: Vec<u8>) -> Option<SendGroupFileRsp> {
+        let response = oidb_response!(0x6d9, 4, data.as_slice())?;
+        match SendGroupFileRsp::decode(response.as_slice()) {
+            Ok(rsp) => Some(rsp),
+            Err(e) => {
+                error!("Failed to decode SendGroupFileRsp(0x6d9_4): {:?}, data: {}", e, hex::encode(&response));
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use prost::Message;
+use ntrim_macros::command;
+use crate::pb::trpc::highway::{HttpConnBody, HttpConnReq, HttpConnRsp};
+
+struct FetchHighwaySessionBuilder;
+
+#[command("HttpConn.0x6ff_501", "fetch_highway_session", Protobuf, Service)]
+impl FetchHighwaySessionBuilder {
+    async fn generate(bot: &Arc<Bot>) -> Option<Vec<u8>> {
+        Some(HttpConnReq {
+            body: HttpConnBody {
+                field1: Some(0),
+                field2: Some(0),
+                field3: Some(16),
+                field4: Some(1),
+                field6: Some(3),
+                service_types: vec![1, 5, 10, 21],
+                field9: Some(2),
+                field10: Some(9),
+                field11: Some(8),
+                ver: Some("1.0.1".to_string()),
+            },
+        }.encode_to_vec())
+    }
+
+    async fn parse(bot: &Arc<Bot>, data: Vec<u8>) -> Option<HttpConnRsp> {
+        match HttpConnRsp::decode(data.as_slice()) {
+            Ok(rsp) => Some(rsp),
+            Err(e) => {
+                error!("Failed to decode HttpConnRsp: {:?}, data: {}", e, hex::encode(&data));
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1 @@
+pub mod fetch_highway_session;
@@ -3,6 +3,11 @@ pub mod register_old;
 pub mod wtlogin;
 mod status;
 mod richmedia;
+mod group;
+mod friend;
+mod profile;
+mod highway;
+mod group_file;
 
 /// timeout不可以小于5s时间，否则可能导致内存泄露
 #[macro_export]
@@ -18,6 +23,23 @@ macro_rules! await_response {
     };
 }
 
+/// 发送命令并等待其解析结果，`$future`为`Bot`上由`#[command]`生成的方法调用
+#[macro_export]
+macro_rules! await_command {
+    ($future:expr) => {
+        $crate::await_response!(tokio::time::Duration::from_secs(5), async {
+            match $future.await {
+                Some(rx) => rx.await.map_err(|e| anyhow::Error::new(e)),
+                None => Err(anyhow::Error::msg("Tcp connection exception")),
+            }
+        }, |value| {
+            Ok(value)
+        }, |e| {
+            Err(e)
+        })
+    };
+}
+
 #[macro_export]
 macro_rules! oidb_request {
     ($cmd:expr, $service:expr, $buffer:expr) => {
@@ -52,4 +74,22 @@ macro_rules! oidb_response {
             None
         }
     }};
-}
\ No newline at end of file
+}
+/// 与`oidb_response!`相同，但保留服务器返回的错误码与错误信息，
+/// 用于调用方需要区分失败原因的操作
+#[macro_export]
+macro_rules! oidb_result {
+    ($cmd:expr, $service:expr, $buffer:expr) => {{
+        match pb::oidb::TrpcOidbResponse::decode($buffer) {
+            Ok(rsp) if rsp.cmd == $cmd && rsp.service == $service => Some(rsp),
+            Ok(rsp) => {
+                info!("Invalid TrpcOidbResponse: {:?}", rsp);
+                None
+            }
+            Err(e) => {
+                info!("Failed to decode TrpcOidbResponse: {:?}", e);
+                None
+            }
+        }
+    }};
+}
@@ -0,0 +1,29 @@
+use log::info;
+use prost::Message;
+use ntrim_macros::command;
+use crate::{oidb_request, oidb_response, pb};
+use crate::pb::oidb::cmd0xfe1::{ * };
+
+struct FetchUserInfoBuilder;
+
+#[command("OidbSvcTrpcTcp.0xfe1_2", "fetch_user_info", Protobuf, Service)]
+impl FetchUserInfoBuilder {
+    async fn generate(bot: &Arc<Bot>, uin: u64, keys: Vec<u32>) -> Option<Vec<u8>> {
+        oidb_request!(0xfe1, 2, FetchUserInfoByUinReq {
+            uin,
+            field2: Some(0),
+            keys: keys.into_iter().map(|key| UserInfoKey { key }).collect(),
+        }.encode_to_vec())
+    }
+
+    async fn parse(bot: &Arc<Bot>, data: Vec<u8>) -> Option<FetchUserInfoRsp> {
+        let response = oidb_response!(0xfe1, 2, data.as_slice())?;
+        match FetchUserInfoRsp::decode(response.as_slice()) {
+            Ok(rsp) => Some(rsp),
+            Err(e) => {
+                error!("Failed to decode FetchUserInfoRsp(2): {:?}, data: {}", e, hex::encode(&response));
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,29 @@
+use log::info;
+use prost::Message;
+use ntrim_macros::command;
+use crate::{oidb_request, oidb_response, pb};
+use crate::pb::oidb::cmd0xfe1::{ * };
+
+struct FetchUserInfoByUidBuilder;
+
+#[command("OidbSvcTrpcTcp.0xfe1_8", "fetch_user_info_by_uid", Protobuf, Service)]
+impl FetchUserInfoByUidBuilder {
+    async fn generate(bot: &Arc<Bot>, uid: String, keys: Vec<u32>) -> Option<Vec<u8>> {
+        oidb_request!(0xfe1, 8, FetchUserInfoByUidReq {
+            uid,
+            field2: Some(0),
+            keys: keys.into_iter().map(|key| UserInfoKey { key }).collect(),
+        }.encode_to_vec())
+    }
+
+    async fn parse(bot: &Arc<Bot>, data: Vec<u8>) -> Option<FetchUserInfoRsp> {
+        let response = oidb_response!(0xfe1, 8, data.as_slice())?;
+        match FetchUserInfoRsp::decode(response.as_slice()) {
+            Ok(rsp) => Some(rsp),
+            Err(e) => {
+                error!("Failed to decode FetchUserInfoRsp(8): {:?}, data: {}", e, hex::encode(&response));
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod fetch_user_info;
+pub mod fetch_user_info_by_uid;
+pub mod send_profile_like;
@@ -0,0 +1,23 @@
+use log::info;
+use prost::Message;
+use ntrim_macros::command;
+use crate::{oidb_request, oidb_result, pb};
+use crate::pb::oidb::TrpcOidbResponse;
+use crate::pb::oidb::cmd0x7e5::ProfileLikeReq;
+
+struct SendProfileLikeBuilder;
+
+#[command("OidbSvcTrpcTcp.0x7e5_104", "send_profile_like", Protobuf, Service)]
+impl SendProfileLikeBuilder {
+    async fn generate(bot: &Arc<Bot>, uid: String, count: u32) -> Option<Vec<u8>> {
+        oidb_request!(0x7e5, 104, ProfileLikeReq {
+            target_uid: uid,
+            source: 71,
+            count,
+        }.encode_to_vec())
+    }
+
+    async fn parse(bot: &Arc<Bot>, data: Vec<u8>) -> Option<TrpcOidbResponse> {
+        oidb_result!(0x7e5, 104, data.as_slice())
+    }
+}
@@ -0,0 +1,71 @@
+use std::future::Future;
+use std::sync::Arc;
+use anyhow::Error;
+use log::{info, warn};
+use crate::client::trpc::TrpcClient;
+use crate::commands::wtlogin::device_lock_login::DeviceLockLogin;
+use crate::commands::wtlogin::request_sms::RequestSms;
+use crate::commands::wtlogin::submit_sms::SubmitSms;
+use crate::commands::wtlogin::submit_ticket::SubmitTicket;
+use crate::commands::wtlogin::wtlogin_request::{WtloginBuilder, WtloginFactory};
+use crate::commands::wtlogin::WtloginRequest;
+use crate::events::wtlogin_event::WtloginResponse;
+
+/// 登录验证回调，由前端(例如命令行)向用户索取验证信息
+/// 返回None表示放弃登录，返回的future需要是Send以便在tokio任务中等待
+pub trait LoginChallengeHandler {
+    /// 完成滑块验证，返回得到的ticket
+    fn on_captcha(&self, url: &str) -> impl Future<Output = Option<String>> + Send;
+
+    /// 验证码已发送到手机，返回用户输入的短信验证码
+    fn on_sms(&self, phone: &str) -> impl Future<Output = Option<String>> + Send;
+}
+
+/// 处理登录返回的验证，直到登录成功或失败
+pub async fn resolve_login_challenge<H: LoginChallengeHandler>(
+    trpc: &Arc<TrpcClient>,
+    resp: WtloginResponse,
+    handler: &H
+) -> WtloginResponse {
+    let mut resp = resp;
+    loop {
+        resp = match resp {
+            WtloginResponse::NeedCaptcha { url, ctx } => {
+                let Some(ticket) = handler.on_captcha(&url).await else {
+                    return WtloginResponse::Fail(Error::msg("Captcha verification canceled"));
+                };
+                send(WtloginBuilder::<SubmitTicket>::build(trpc.clone(), (ticket, ctx))).await
+            }
+            WtloginResponse::DeviceLocked { phone, verify_url, message, ctx } => {
+                let Some(phone) = phone else {
+                    return WtloginResponse::Fail(Error::msg(format!(
+                        "Device locked: {}, please verify at {} and login again",
+                        message, verify_url.unwrap_or_default()
+                    )));
+                };
+                match send(WtloginBuilder::<RequestSms>::build(trpc.clone(), ctx.clone())).await {
+                    // 验证码发送成功时服务器会再次返回设备锁
+                    WtloginResponse::DeviceLocked { .. } | WtloginResponse::SmsCodeSent { .. } => {
+                        info!("Sms code sent to {}", phone)
+                    }
+                    WtloginResponse::Fail(e) => return WtloginResponse::Fail(e),
+                    resp => warn!("Unexpected response when requesting sms: {:?}", resp),
+                }
+                let Some(code) = handler.on_sms(&phone).await else {
+                    return WtloginResponse::Fail(Error::msg("Sms verification canceled"));
+                };
+                send(WtloginBuilder::<SubmitSms>::build(trpc.clone(), (code, ctx))).await
+            }
+            WtloginResponse::DeviceLockLogin { ctx } => {
+                send(WtloginBuilder::<DeviceLockLogin>::build(trpc.clone(), ctx)).await
+            }
+            resp => return resp,
+        };
+    }
+}
+
+async fn send<T: WtloginRequest + Sync + Send + 'static>(builder: Arc<WtloginBuilder<T>>) -> WtloginResponse {
+    builder.send().await.await.unwrap_or_else(|e| {
+        WtloginResponse::Fail(Error::msg(format!("Failed to recv wtlogin response: {}", e)))
+    })
+}
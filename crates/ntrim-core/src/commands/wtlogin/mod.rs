@@ -1,5 +1,16 @@
 mod tlv;
 pub mod refresh_sig;
+pub mod refresh_st;
+pub mod qrcode_login;
+pub mod password_login;
+pub mod trans_emp;
+pub mod submit_ticket;
+pub mod request_sms;
+pub mod submit_sms;
+pub mod device_lock_login;
+pub mod request_phone_sms;
+pub mod submit_phone_sms;
+pub mod challenge;
 pub use wtlogin_request::WtloginRequest;
 
 pub mod wtlogin_request {
@@ -9,16 +20,16 @@ pub mod wtlogin_request {
     use anyhow::Error;
     use bytes::{Buf, BufMut, Bytes, BytesMut};
     use chrono::DateTime;
+    use prost::Message;
     use log::{error, info, warn};
     use tokio::sync::oneshot::{Receiver, Sender};
     use ntrim_tools::bytes::{BytePacketBuilder, BytePacketReader, PacketFlag};
-    use ntrim_tools::crypto::ecdh::ecdh_share_key;
     use ntrim_tools::crypto::qqtea::{qqtea_decrypt, qqtea_encrypt};
     use crate::client::packet::FromServiceMsg;
     use crate::client::packet::packet::{CommandType, UniPacket};
     use crate::client::qsecurity::QSecurity;
     use crate::client::trpc::TrpcClient;
-    use crate::events::wtlogin_event::WtloginResponse;
+    use crate::events::wtlogin_event::{WtloginContext, WtloginError, WtloginResponse};
     use crate::session::SsoSession;
     use crate::session::ticket::{SigType, Ticket, TicketManager};
 
@@ -49,33 +60,19 @@ pub mod wtlogin_request {
     where T: WtloginRequest
     {
         async fn generate_body(&self, session: &SsoSession, seq: u32) -> Vec<u8> {
-            let encrypt_body = self.request.generate_encrypt_body();
-            let encrypt_key = self.request.get_encrypt_key(session);
-            let encrypt_public_key = encrypt_key.1;
-            let encrypt_share_key = encrypt_key.2;
+            let encrypt_body = self.request.generate_encrypt_body(session);
+            let encrypt_key = self.request.get_encrypt_key(session).await;
             let tlv_body = self.request.generate_tlv_body(
                 session, self.trpc.qsec.clone(),
                 self.wt_sub_command, seq
             ).await;
-            let encrypt_tlv_body = qqtea_encrypt(tlv_body.as_slice(), encrypt_share_key.as_slice());
-            let mut buf = BytesMut::new();
-            buf.put_u8(2);
-            buf.put_u16(27 + encrypt_body.len() as u16 + 2 + encrypt_public_key.len() as u16 + 2 + encrypt_tlv_body.len() as u16);
-            buf.put_u16(8001);
-            buf.put_u16(self.wt_command);
-            buf.put_u16(1);
-            buf.put_u32(session.uin as u32);
-            buf.put_u8(3);
-            buf.put_u8(encrypt_key.0);
-            buf.put_u8(0);
-            buf.put_u32(2); // android -> 2
-            buf.put_u32(0);
-            buf.put_u32(0);
-            buf.put_slice(encrypt_body.as_slice());
-            buf.put_bytes_with_flags(encrypt_public_key.as_slice(), PacketFlag::I16Len);
-            buf.put_slice(encrypt_tlv_body.as_slice());
-            buf.put_u8(3);
-            buf.to_vec()
+            build_oicq_packet(
+                session.uin as u32,
+                self.wt_command,
+                encrypt_key,
+                encrypt_body.as_slice(),
+                tlv_body.as_slice()
+            )
         }
 
         pub async fn send(self: Arc<Self>) -> Receiver<WtloginResponse> {
@@ -107,27 +104,16 @@ pub mod wtlogin_request {
 
         async fn handle_response(&self, msg: FromServiceMsg, cb: Sender<WtloginResponse>) {
             let mut session = self.trpc.session.write().await;
-            let mut reader = BytesMut::from(msg.wup_buffer.as_slice());
-            reader.advance(1 + 2 + 2 + 2 + 2);
-            // 02 (dis) xx xx (dis) 1f 41 (dis) 08 01 (dis) 00 01 (dis)
-            let uin = reader.get_u32() as u64;
-            // println(uin)
-            reader.advance(2);
-            // 00 00 (dis)
-//            val subCommand = reader.readShort().toInt() // subCommand discardExact 2
-//            println(subCommand)
-            let result = reader.get_u8();
+            let (uin, result, tlv_body) = decode_oicq_packet(msg.wup_buffer.as_slice());
             // 235 协议版本过低
             //let teaKey = if (result == 180) manager.session.randomKey else key
 
             let key = match self.command_type {
-                CommandType::ExchangeSt => ecdh_share_key().await.as_slice(),
+                CommandType::ExchangeSt | CommandType::Login => session.ecdh.share_key.as_slice(),
                 CommandType::ExchangeSig => session.wt_session_key.as_slice(),
                 _ => panic!("Not supported wtlogin command: {:?}", self.command_type),
             };
 
-            let mut tlv_body = vec![0u8; reader.remaining() - 1];
-            reader.copy_to_slice(&mut tlv_body);
             let tlv_body = qqtea_decrypt(tlv_body.as_slice(), key).unwrap();
             let mut tlv_body = BytesMut::from(tlv_body.as_slice());
             //let wt_sub_command = tlv_body.get_u16();
@@ -136,11 +122,14 @@ pub mod wtlogin_request {
             if let Some(t119) = tlv_map.get(&0x119) {
                 let decrypt_key = match self.command_type {
                     CommandType::ExchangeSt => md5::compute(session.get_session_key(self.command_type)).0.to_vec(),
-                    CommandType::ExchangeSig => session.tgtgt_key.clone(),
+                    CommandType::ExchangeSig | CommandType::Login => session.tgtgt_key.clone(),
                     _ => panic!("Not supported wtlogin command: {:?}", self.command_type),
                 };
                 let t119 = qqtea_decrypt(t119.as_ref(), decrypt_key.as_slice()).unwrap();
                 let mut t119 = BytesMut::from(t119.as_slice());
+                if session.uin == 0 {
+                    session.uin = uin;
+                }
                 Self::parse_tlv(&mut t119).iter().for_each(|(k, v)| {
                     match *k {
                         0x103 => {
@@ -151,7 +140,7 @@ pub mod wtlogin_request {
                             info!("Refresh encrypt_a1 successfully!");
                         }
                         0x10a => {
-                            let mut ticket = session.ticket_mut(SigType::A2).unwrap();
+                            let mut ticket = ticket_entry(&mut session, SigType::A2);
                             ticket.sig = Some(v.to_vec());
                         }
                         0x10c => {
@@ -159,17 +148,25 @@ pub mod wtlogin_request {
                             info!("Refresh gt_key successfully!");
                         }
                         0x10d => {
-                            let mut ticket = session.ticket_mut(SigType::A2).unwrap();
+                            let mut ticket = ticket_entry(&mut session, SigType::A2);
                             ticket.sig_key = v.to_vec();
                         }
                         0x10e => {
-                            let mut ticket = session.ticket_mut(SigType::ST).unwrap();
+                            let mut ticket = ticket_entry(&mut session, SigType::ST);
                             ticket.sig_key = v.to_vec();
                         }
                         0x114 => {
-                            let mut ticket = session.ticket_mut(SigType::ST).unwrap();
+                            let mut ticket = ticket_entry(&mut session, SigType::ST);
                             ticket.sig = Some(v.to_vec());
                         }
+                        0x113 => {
+                            // 手机号登录时请求中没有uin
+                            if v.len() >= 4 {
+                                session.uin = BytesMut::from(v.as_ref()).get_u32() as u64;
+                            } else {
+                                warn!("Invalid tlv_t113, len: {}", v.len());
+                            }
+                        }
                         0x118 => {}
                         0x11a => {
                             let mut buf = BytesMut::from(v.as_ref());
@@ -201,6 +198,7 @@ pub mod wtlogin_request {
                         0x130 => {}
                         0x133 => {
                             session.wt_session_ticket = v.to_vec();
+                            session.wt_session_create_time = chrono::Local::now().timestamp() as u64;
                         }
                         0x134 => {
                             session.wt_session_key = v.to_vec();
@@ -226,7 +224,7 @@ pub mod wtlogin_request {
                                     0x103 => {},
                                     0x120 => {},
                                     0x143 => {
-                                        let mut ticket = session.ticket_mut(SigType::D2).unwrap();
+                                        let mut ticket = ticket_entry(&mut session, SigType::D2);
                                         ticket.create_time = current_time_sec;
                                         ticket.expire_time = expire_time;
                                     }
@@ -237,7 +235,7 @@ pub mod wtlogin_request {
                             }
                         }
                         0x143 => {
-                            let mut ticket = session.ticket_mut(SigType::D2).unwrap();
+                            let mut ticket = ticket_entry(&mut session, SigType::D2);
                             ticket.sig = Some(v.to_vec());
                         }
                         0x163 => {}
@@ -252,7 +250,7 @@ pub mod wtlogin_request {
                             // da2
                         }
                         0x305 => {
-                            let mut ticket = session.ticket_mut(SigType::D2).unwrap();
+                            let mut ticket = ticket_entry(&mut session, SigType::D2);
                             ticket.sig_key = v.to_vec();
                             info!("Refresh d2key successfully!");
                         }
@@ -261,13 +259,18 @@ pub mod wtlogin_request {
                         }
                         0x512 => {
                             // web key
-                            //let mut buf = v.clone().as_mut();
-                            //let size = buf.get_u16();
-                            //for _ in 0..size {
-                            //    let domain = buf.get_str_with_flags(PacketFlag::I16Len).unwrap();
-                            //    let pskey = buf.get_str_with_flags(PacketFlag::I16Len).unwrap();
-                            //    let p4token = buf.get_str_with_flags(PacketFlag::I16Len).unwrap();
-                            //}
+                            let mut buf = BytesMut::from(v.as_ref());
+                            let size = buf.get_u16();
+                            for _ in 0..size {
+                                let domain = buf.get_str_with_flags(PacketFlag::I16Len).unwrap_or_default();
+                                let pskey = buf.get_str_with_flags(PacketFlag::I16Len).unwrap_or_default();
+                                let pt4token = buf.get_str_with_flags(PacketFlag::I16Len).unwrap_or_default();
+                                if domain.is_empty() || pskey.is_empty() {
+                                    continue;
+                                }
+                                session.pskeys.insert(domain, (pskey, pt4token));
+                            }
+                            info!("Refresh pskey successfully, {} domains!", session.pskeys.len());
                         }
                         0x522 => {}
                         0x528 => {}
@@ -283,7 +286,13 @@ pub mod wtlogin_request {
                             }
                         }
                         0x543 => {
-
+                            if let Ok(t543) = crate::pb::wtlogin::Tlv543::decode(v.as_ref()) {
+                                if let Some(uid) = t543.layer1.and_then(|l| l.layer2).map(|l| l.uid) {
+                                    if !uid.is_empty() {
+                                        session.uid = uid;
+                                    }
+                                }
+                            }
                         }
                         0x550 => {}
 
@@ -294,27 +303,58 @@ pub mod wtlogin_request {
                     cb.send(WtloginResponse::Success()).expect("Failed to send wtlogin response");
                 }
             } else {
-                let t146 = tlv_map.get(&0x146);
-                let t508 = tlv_map.get(&0x508);
-                //warn!("T146 => {}", hex::encode(t146.map_or_else(|| vec![], |v| v.to_vec())));
-                //warn!("T508 => {}", hex::encode(t508.map_or_else(|| vec![], |v| v.to_vec())));
-                if let Some(t146) = t146 {
-                    let mut t146 = BytesMut::from(t146.to_vec().as_slice());
-                    t146.advance(4);
-                    let msg_len = t146.get_u16() as u32;
-                    let msg = t146.copy_to_bytes(msg_len as usize);
-                    let msg = String::from_utf8(msg.to_vec()).unwrap();
-                    let reason_len = t146.get_u16() as u32;
-                    let reason = t146.copy_to_bytes(reason_len as usize);
-                    let reason = String::from_utf8(reason.to_vec()).unwrap();
-                    error!("Wtlogin failed, result: 0x{:x}, user_id: {}, msg: {}, reason: {}", result, uin, msg, reason);
-                } else {
-                    error!("Wtlogin failed, result: 0x{:x}, user_id: {}, Unknown Error", result, uin);
-                }
+                let resp = Self::parse_challenge(result, &tlv_map).unwrap_or_else(|| {
+                    let t146 = tlv_map.get(&0x146);
+                    //let t508 = tlv_map.get(&0x508);
+                    let err = WtloginError::from_t146(result, t146.map(|v| v.as_ref()));
+                    error!("Wtlogin failed, user_id: {}, {}", uin, err);
+                    WtloginResponse::Fail(Error::new(err))
+                });
                 if cb.is_closed() { return; }
-                cb.send(
-                    WtloginResponse::Fail(Error::msg(format!("Wtlogin failed, result: 0x{:x}, user_id: {}", result, uin)))
-                ).unwrap();
+                cb.send(resp).unwrap();
+            }
+        }
+
+        /// 解析登录过程中需要用户参与的验证
+        fn parse_challenge(result: u8, tlv_map: &HashMap<u16, Bytes>) -> Option<WtloginResponse> {
+            let get_tlv = |ver: u16| tlv_map.get(&ver).map(|v| v.to_vec()).unwrap_or_default();
+            let ctx = WtloginContext {
+                t104: get_tlv(0x104),
+                t174: get_tlv(0x174),
+                t402: get_tlv(0x402),
+            };
+            match result {
+                2 => {
+                    let url = String::from_utf8(tlv_map.get(&0x192)?.to_vec()).ok()?;
+                    info!("Wtlogin need captcha: {}", url);
+                    Some(WtloginResponse::NeedCaptcha { url, ctx })
+                }
+                160 | 239 => {
+                    let verify_url = tlv_map.get(&0x204)
+                        .and_then(|v| String::from_utf8(v.to_vec()).ok());
+                    // country code + phone
+                    let phone = tlv_map.get(&0x178).and_then(|v| {
+                        let mut v = BytesMut::from(v.as_ref());
+                        let country_code = v.get_str_with_flags(PacketFlag::I16Len).ok()?;
+                        let phone = v.get_str_with_flags(PacketFlag::I16Len).ok()?;
+                        Some(format!("+{} {}", country_code, phone))
+                    }).filter(|_| !ctx.t174.is_empty());
+                    let message = tlv_map.get(&0x17e)
+                        .map(|v| String::from_utf8_lossy(v.as_ref()).to_string())
+                        .or_else(|| tlv_map.get(&0x146).map(|v| WtloginError::parse_t146(v.as_ref()).1))
+                        .unwrap_or_default();
+                    info!("Wtlogin device locked, phone: {:?}, verify_url: {:?}", phone, verify_url);
+                    Some(WtloginResponse::DeviceLocked { phone, verify_url, message, ctx })
+                }
+                204 => {
+                    info!("Wtlogin device lock login");
+                    Some(WtloginResponse::DeviceLockLogin { ctx })
+                }
+                0 if !ctx.t104.is_empty() => {
+                    info!("Wtlogin sms code sent");
+                    Some(WtloginResponse::SmsCodeSent { ctx })
+                }
+                _ => None
             }
         }
 
@@ -329,10 +369,96 @@ pub mod wtlogin_request {
         }
     }
 
+    /// 没有对应票据时插入空票据，首次登录时会话中什么都没有
+    /// 调用方会修改返回的票据，这里通知持久化服务保存会话
+    fn ticket_entry(session: &mut SsoSession, id: SigType) -> &mut Ticket {
+        session.mark_changed();
+        session.tickets.entry(id).or_insert_with(|| Ticket {
+            id,
+            sig_key: Vec::new(),
+            sig: None,
+            create_time: 0,
+            expire_time: 0,
+        })
+    }
+
+    /// 封装oicq包，encrypt_key为(加密类型, 公钥, 共享密钥)
+    pub(crate) fn build_oicq_packet(
+        uin: u32,
+        wt_command: u16,
+        encrypt_key: (u8, Vec<u8>, Vec<u8>),
+        encrypt_body: &[u8],
+        tlv_body: &[u8]
+    ) -> Vec<u8> {
+        let (encrypt_type, encrypt_public_key, encrypt_share_key) = encrypt_key;
+        let encrypt_tlv_body = qqtea_encrypt(tlv_body, encrypt_share_key.as_slice());
+        let mut buf = BytesMut::new();
+        buf.put_u8(2);
+        buf.put_u16(27 + encrypt_body.len() as u16 + 2 + encrypt_public_key.len() as u16 + 2 + encrypt_tlv_body.len() as u16);
+        buf.put_u16(8001);
+        buf.put_u16(wt_command);
+        buf.put_u16(1);
+        buf.put_u32(uin);
+        buf.put_u8(3);
+        buf.put_u8(encrypt_type);
+        buf.put_u8(0);
+        buf.put_u32(2); // android -> 2
+        buf.put_u32(0);
+        buf.put_u32(0);
+        buf.put_slice(encrypt_body);
+        buf.put_bytes_with_flags(encrypt_public_key.as_slice(), PacketFlag::I16Len);
+        buf.put_slice(encrypt_tlv_body.as_slice());
+        buf.put_u8(3);
+        buf.to_vec()
+    }
+
+    /// 拆开oicq包，返回(uin, result, 加密的body)
+    pub(crate) fn decode_oicq_packet(buf: &[u8]) -> (u64, u8, Vec<u8>) {
+        let mut reader = BytesMut::from(buf);
+        reader.advance(1 + 2 + 2 + 2 + 2);
+        // 02 (dis) xx xx (dis) 1f 41 (dis) 08 01 (dis) 00 01 (dis)
+        let uin = reader.get_u32() as u64;
+        reader.advance(2);
+        // 00 00 (dis)
+        let result = reader.get_u8();
+        let mut body = vec![0u8; reader.remaining() - 1];
+        reader.copy_to_slice(&mut body);
+        (uin, result, body)
+    }
+
+    /// ECDH加密使用的(加密类型, 公钥, 共享密钥)
+    pub(crate) fn ecdh_encrypt_key(session: &SsoSession) -> (u8, Vec<u8>, Vec<u8>) {
+        (0x87, session.ecdh.public_key.clone(), session.ecdh.share_key.clone())
+    }
+
+    /// 登录类请求(0x810)的tlv_t544
+    pub(crate) async fn login_tlv544(session: &SsoSession, qsec: Arc<dyn QSecurity>, sub_command: u16) -> Vec<u8> {
+        let data = format!("810_{:x}", sub_command);
+        let sdk_version = session.protocol.sdk_version.clone();
+        let mut salt = BytesMut::new();
+        salt.put_u64(session.uin);
+        salt.put_bytes_with_flags(&session.guid, PacketFlag::I16Len);
+        salt.put_bytes_with_flags(sdk_version.as_bytes(), PacketFlag::I16Len);
+        salt.put_u32(sub_command as u32);
+        let salt = salt.to_vec();
+        qsec.energy(data, salt).await
+    }
+
+    /// ECDH加密头，random_key仅用于服务端返回错误时解密
+    pub(crate) fn ecdh_encrypt_body(session: &SsoSession) -> Vec<u8> {
+        let mut buf = BytesMut::new();
+        buf.put_u8(2);
+        buf.put_u8(1);
+        buf.put_slice(&rand::random::<[u8; 16]>());
+        buf.put_u16(0x131);
+        buf.put_u16(session.ecdh.server_public_key_version);
+        buf.to_vec()
+    }
+
     pub trait WtloginRequest {
-        fn get_encrypt_key(&self, session: &SsoSession) -> (u8, Vec<u8>, Vec<u8>);
+        async fn get_encrypt_key(&self, session: &SsoSession) -> (u8, Vec<u8>, Vec<u8>);
 
-        fn generate_encrypt_body(&self) -> Vec<u8>  { vec![] }
+        fn generate_encrypt_body(&self, _session: &SsoSession) -> Vec<u8>  { vec![] }
 
         async fn generate_tlv544(&self, session: &SsoSession, qsec: Arc<dyn QSecurity>) -> Vec<u8>;
 
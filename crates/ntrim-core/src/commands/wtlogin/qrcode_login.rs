@@ -0,0 +1,116 @@
+use std::sync::Arc;
+use bytes::{BufMut, BytesMut};
+use crate::client::packet::packet::CommandType;
+use crate::client::qsecurity::QSecurity;
+use crate::client::trpc::TrpcClient;
+use crate::commands::wtlogin::tlv::{*};
+use crate::commands::wtlogin::wtlogin_request::{ecdh_encrypt_body, ecdh_encrypt_key, login_tlv544, WtloginFactory, WtloginBuilder};
+use crate::commands::wtlogin::WtloginRequest;
+use crate::session::SsoSession;
+
+/// 扫码确认后使用临时凭证完成登录
+/// 发送前需要将会话的uin与tgtgt_key设置为扫码结果中的值
+pub struct QrCodeLogin {
+    pub tmp_pwd: Vec<u8>,
+    pub no_pic_sig: Vec<u8>,
+    pub tgt_qr: Vec<u8>,
+    pub domains: Vec<String>
+}
+
+impl WtloginFactory<QrCodeLogin> for WtloginBuilder<QrCodeLogin> {
+    /// (tmp_pwd, no_pic_sig, tgt_qr, domains)
+    type Params = (Vec<u8>, Vec<u8>, Vec<u8>, Vec<String>);
+
+    fn build(
+        trpc: Arc<TrpcClient>,
+        params: Self::Params
+    ) -> Arc<WtloginBuilder<QrCodeLogin>> {
+        Arc::new(WtloginBuilder {
+            trpc,
+            command: "wtlogin.login".to_string(),
+            command_type: CommandType::Login,
+            wt_command: 0x810,
+            wt_sub_command: 0x9,
+            request: QrCodeLogin {
+                tmp_pwd: params.0,
+                no_pic_sig: params.1,
+                tgt_qr: params.2,
+                domains: params.3
+            }
+        })
+    }
+}
+
+impl WtloginRequest for QrCodeLogin {
+    async fn get_encrypt_key(&self, session: &SsoSession) -> (u8, Vec<u8>, Vec<u8>) {
+        ecdh_encrypt_key(session)
+    }
+
+    fn generate_encrypt_body(&self, session: &SsoSession) -> Vec<u8> {
+        ecdh_encrypt_body(session)
+    }
+
+    async fn generate_tlv544(&self, session: &SsoSession, qsec: Arc<dyn QSecurity>) -> Vec<u8> {
+        login_tlv544(session, qsec, 0x9).await
+    }
+
+    async fn generate_tlv_body(
+        &self,
+        session: &SsoSession,
+        qsec: Arc<dyn QSecurity>,
+        wt_command: u16,
+        seq: u32
+    ) -> Vec<u8> {
+        let uin = session.uin as u32;
+        let protocol = &session.protocol;
+        let device = &session.device;
+
+        let mut buf = BytesMut::new();
+        buf.put_u16(wt_command);
+
+        buf.put_u16(26);
+        t18(&mut buf, uin);
+        t1(&mut buf, uin);
+        t106_data(&mut buf, self.tmp_pwd.as_slice());
+        t116(&mut buf, protocol.misc_bitmap, protocol.sub_sig_map);
+        t100(&mut buf, protocol.sso_version, protocol.sub_app_id, protocol.main_sig_map, 16);
+        t107(&mut buf);
+        t108(&mut buf, &session.ksid);
+        t142(&mut buf, &protocol.apk_id);
+        t144(
+            &mut buf,
+            session.tgtgt_key.as_slice(),
+            &session.guid,
+            &device.android_id,
+            &device.brand,
+            &device.device_name,
+            &device.code,
+            &device.os_ver,
+            &device.os_type,
+            &device.apn_name,
+            &device.apn
+        );
+        t145(&mut buf, &session.guid);
+        t147(&mut buf, &protocol.apk_ver, &protocol.apk_sign);
+        t16a(&mut buf, self.no_pic_sig.as_slice());
+        t154(&mut buf, seq);
+        t141(&mut buf, &device.apn_name, &device.apn);
+        t8(&mut buf, protocol.locale_id);
+        t511(&mut buf, &self.domains);
+        t187(&mut buf, &device.mac_address);
+        t188(&mut buf, &device.android_id);
+        t191(&mut buf, 0x0);
+        t177(&mut buf, protocol.build_time, &protocol.sdk_version);
+        t516(&mut buf);
+        t521(&mut buf);
+        t318(&mut buf, self.tgt_qr.as_slice());
+
+        let tlv544 = self.generate_tlv544(session, qsec).await;
+        t544(&mut buf, tlv544.as_slice());
+
+        t553(&mut buf, device.fingerprint.as_slice());
+        t545(&mut buf, &device.qimei);
+
+        buf.to_vec()
+    }
+}
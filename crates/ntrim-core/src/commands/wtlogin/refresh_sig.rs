@@ -63,7 +63,7 @@ impl WtloginFactory<RefreshSig> for WtloginBuilder<RefreshSig> {
 ///let resp = rx.await.unwrap();
 ///info!("Refresh sig response: {:?}", resp);
 impl WtloginRequest for RefreshSig {
-    fn get_encrypt_key(&self, session: &SsoSession) -> (u8, Vec<u8>, Vec<u8>) {
+    async fn get_encrypt_key(&self, session: &SsoSession) -> (u8, Vec<u8>, Vec<u8>) {
         let st_session_ticket  = session.wt_session_ticket.clone();
         let st_session_key = session.wt_session_key.clone();
         (0x45, st_session_ticket, st_session_key)
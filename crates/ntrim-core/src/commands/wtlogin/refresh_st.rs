@@ -0,0 +1,111 @@
+use std::sync::Arc;
+use bytes::{BufMut, BytesMut};
+use crate::client::packet::packet::CommandType;
+use crate::client::qsecurity::QSecurity;
+use crate::client::trpc::TrpcClient;
+use crate::commands::wtlogin::tlv::{*};
+use crate::commands::wtlogin::wtlogin_request::{ecdh_encrypt_body, ecdh_encrypt_key, login_tlv544, WtloginFactory, WtloginBuilder};
+use crate::commands::wtlogin::WtloginRequest;
+use crate::session::SsoSession;
+use crate::session::ticket::{SigType, TicketManager};
+
+/// 使用A2与D2换取新的A2/D2/ST以及WT会话票据
+/// 适用于WT会话过期，RefreshSig已无法使用的情况
+pub struct RefreshSt {
+    pub domains: Vec<String>
+}
+
+impl WtloginFactory<RefreshSt> for WtloginBuilder<RefreshSt> {
+    type Params = Vec<String>;
+
+    fn build(
+        trpc: Arc<TrpcClient>,
+        params: Self::Params
+    ) -> Arc<WtloginBuilder<RefreshSt>> {
+        Arc::new(WtloginBuilder {
+            trpc,
+            command: "wtlogin.exchange_emp".to_string(),
+            command_type: CommandType::ExchangeSt,
+            wt_command: 0x810,
+            wt_sub_command: 0xb,
+            request: RefreshSt {
+                domains: params
+            }
+        })
+    }
+}
+
+impl WtloginRequest for RefreshSt {
+    async fn get_encrypt_key(&self, session: &SsoSession) -> (u8, Vec<u8>, Vec<u8>) {
+        ecdh_encrypt_key(session)
+    }
+
+    fn generate_encrypt_body(&self, session: &SsoSession) -> Vec<u8> {
+        ecdh_encrypt_body(session)
+    }
+
+    async fn generate_tlv544(&self, session: &SsoSession, qsec: Arc<dyn QSecurity>) -> Vec<u8> {
+        login_tlv544(session, qsec, 0xb).await
+    }
+
+    async fn generate_tlv_body(
+        &self,
+        session: &SsoSession,
+        qsec: Arc<dyn QSecurity>,
+        wt_command: u16,
+        seq: u32
+    ) -> Vec<u8> {
+        let uin = session.uin as u32;
+        let protocol = &session.protocol;
+        let device = &session.device;
+        let a2 = session.ticket(SigType::A2)
+            .and_then(|t| t.sig.clone())
+            .unwrap_or_default();
+        let d2 = session.ticket(SigType::D2)
+            .and_then(|t| t.sig.clone())
+            .unwrap_or_default();
+
+        let mut buf = BytesMut::new();
+        buf.put_u16(wt_command);
+
+        buf.put_u16(21);
+        t100(&mut buf, protocol.sso_version, protocol.sub_app_id, protocol.main_sig_map, 100);
+        t10a(&mut buf, a2.as_slice());
+        t116(&mut buf, protocol.misc_bitmap, protocol.sub_sig_map);
+        t108(&mut buf, &session.ksid);
+        t144(
+            &mut buf,
+            session.tgtgt_key.as_slice(),
+            &session.guid,
+            &device.android_id,
+            &device.brand,
+            &device.device_name,
+            &device.code,
+            &device.os_ver,
+            &device.os_type,
+            &device.apn_name,
+            &device.apn
+        );
+        t143(&mut buf, d2.as_slice());
+        t142(&mut buf, &protocol.apk_id);
+        t154(&mut buf, seq);
+        t18(&mut buf, uin);
+        t141(&mut buf, &device.apn_name, &device.apn);
+        t8(&mut buf, protocol.locale_id);
+        t147(&mut buf, &protocol.apk_ver, &protocol.apk_sign);
+        t177(&mut buf, protocol.build_time, &protocol.sdk_version);
+        t187(&mut buf, &device.mac_address);
+        t188(&mut buf, &device.android_id);
+        t194(&mut buf, &device.android_id);
+        t511(&mut buf, &self.domains);
+        t202(&mut buf, &device.mac_address, "<unknown ssid>");
+
+        let tlv544 = self.generate_tlv544(session, qsec).await;
+        t544(&mut buf, tlv544.as_slice());
+
+        t553(&mut buf, device.fingerprint.as_slice());
+        t545(&mut buf, &device.qimei);
+
+        buf.to_vec()
+    }
+}
@@ -0,0 +1,103 @@
+use std::sync::Arc;
+use bytes::{BufMut, BytesMut};
+use crate::client::packet::packet::CommandType;
+use crate::client::qsecurity::QSecurity;
+use crate::client::trpc::TrpcClient;
+use crate::commands::wtlogin::tlv::{*};
+use crate::commands::wtlogin::wtlogin_request::{ecdh_encrypt_body, ecdh_encrypt_key, login_tlv544, WtloginFactory, WtloginBuilder};
+use crate::commands::wtlogin::WtloginRequest;
+use crate::session::SsoSession;
+
+/// 手机号登录，向绑定的手机号发送短信验证码
+/// 发送前需要随机生成会话的tgtgt_key
+pub struct RequestPhoneSms {
+    pub phone: String
+}
+
+impl WtloginFactory<RequestPhoneSms> for WtloginBuilder<RequestPhoneSms> {
+    type Params = String;
+
+    fn build(
+        trpc: Arc<TrpcClient>,
+        params: Self::Params
+    ) -> Arc<WtloginBuilder<RequestPhoneSms>> {
+        Arc::new(WtloginBuilder {
+            trpc,
+            command: "wtlogin.name2uin".to_string(),
+            command_type: CommandType::Login,
+            wt_command: 0x812,
+            wt_sub_command: 0x4,
+            request: RequestPhoneSms {
+                phone: params
+            }
+        })
+    }
+}
+
+impl WtloginRequest for RequestPhoneSms {
+    async fn get_encrypt_key(&self, session: &SsoSession) -> (u8, Vec<u8>, Vec<u8>) {
+        ecdh_encrypt_key(session)
+    }
+
+    fn generate_encrypt_body(&self, session: &SsoSession) -> Vec<u8> {
+        ecdh_encrypt_body(session)
+    }
+
+    async fn generate_tlv544(&self, session: &SsoSession, qsec: Arc<dyn QSecurity>) -> Vec<u8> {
+        login_tlv544(session, qsec, 0x4).await
+    }
+
+    async fn generate_tlv_body(
+        &self,
+        session: &SsoSession,
+        qsec: Arc<dyn QSecurity>,
+        wt_command: u16,
+        seq: u32
+    ) -> Vec<u8> {
+        let protocol = &session.protocol;
+        let device = &session.device;
+
+        let mut buf = BytesMut::new();
+        buf.put_u16(wt_command);
+
+        buf.put_u16(21);
+        t8(&mut buf, protocol.locale_id);
+        t112(&mut buf, &self.phone);
+        t116(&mut buf, protocol.misc_bitmap, protocol.sub_sig_map);
+        t100(&mut buf, protocol.sso_version, protocol.sub_app_id, protocol.main_sig_map, 16);
+        t107(&mut buf);
+        t108(&mut buf, &session.ksid);
+        t142(&mut buf, &protocol.apk_id);
+        t144(
+            &mut buf,
+            session.tgtgt_key.as_slice(),
+            &session.guid,
+            &device.android_id,
+            &device.brand,
+            &device.device_name,
+            &device.code,
+            &device.os_ver,
+            &device.os_type,
+            &device.apn_name,
+            &device.apn
+        );
+        t145(&mut buf, &session.guid);
+        t147(&mut buf, &protocol.apk_ver, &protocol.apk_sign);
+        t154(&mut buf, seq);
+        t177(&mut buf, protocol.build_time, &protocol.sdk_version);
+        t187(&mut buf, &device.mac_address);
+        t188(&mut buf, &device.android_id);
+        t516(&mut buf);
+        t521(&mut buf);
+        t17a(&mut buf, 9);
+        t197(&mut buf);
+
+        let tlv544 = self.generate_tlv544(session, qsec).await;
+        t544(&mut buf, tlv544.as_slice());
+
+        t553(&mut buf, device.fingerprint.as_slice());
+        t545(&mut buf, &device.qimei);
+
+        buf.to_vec()
+    }
+}
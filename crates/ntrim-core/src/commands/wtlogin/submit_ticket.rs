@@ -0,0 +1,75 @@
+use std::sync::Arc;
+use bytes::{BufMut, BytesMut};
+use crate::client::packet::packet::CommandType;
+use crate::client::qsecurity::QSecurity;
+use crate::client::trpc::TrpcClient;
+use crate::commands::wtlogin::tlv::{*};
+use crate::commands::wtlogin::wtlogin_request::{ecdh_encrypt_body, ecdh_encrypt_key, login_tlv544, WtloginFactory, WtloginBuilder};
+use crate::commands::wtlogin::WtloginRequest;
+use crate::events::wtlogin_event::WtloginContext;
+use crate::session::SsoSession;
+
+/// 提交滑块验证得到的ticket
+pub struct SubmitTicket {
+    pub ticket: String,
+    pub ctx: WtloginContext
+}
+
+impl WtloginFactory<SubmitTicket> for WtloginBuilder<SubmitTicket> {
+    type Params = (String, WtloginContext);
+
+    fn build(
+        trpc: Arc<TrpcClient>,
+        params: Self::Params
+    ) -> Arc<WtloginBuilder<SubmitTicket>> {
+        Arc::new(WtloginBuilder {
+            trpc,
+            command: "wtlogin.login".to_string(),
+            command_type: CommandType::Login,
+            wt_command: 0x810,
+            wt_sub_command: 0x2,
+            request: SubmitTicket {
+                ticket: params.0,
+                ctx: params.1
+            }
+        })
+    }
+}
+
+impl WtloginRequest for SubmitTicket {
+    async fn get_encrypt_key(&self, session: &SsoSession) -> (u8, Vec<u8>, Vec<u8>) {
+        ecdh_encrypt_key(session)
+    }
+
+    fn generate_encrypt_body(&self, session: &SsoSession) -> Vec<u8> {
+        ecdh_encrypt_body(session)
+    }
+
+    async fn generate_tlv544(&self, session: &SsoSession, qsec: Arc<dyn QSecurity>) -> Vec<u8> {
+        login_tlv544(session, qsec, 0x2).await
+    }
+
+    async fn generate_tlv_body(
+        &self,
+        session: &SsoSession,
+        qsec: Arc<dyn QSecurity>,
+        wt_command: u16,
+        _seq: u32
+    ) -> Vec<u8> {
+        let protocol = &session.protocol;
+
+        let mut buf = BytesMut::new();
+        buf.put_u16(wt_command);
+
+        buf.put_u16(5);
+        t193(&mut buf, &self.ticket);
+        t8(&mut buf, protocol.locale_id);
+        t104(&mut buf, &self.ctx.t104);
+        t116(&mut buf, protocol.misc_bitmap, protocol.sub_sig_map);
+
+        let tlv544 = self.generate_tlv544(session, qsec).await;
+        t544(&mut buf, tlv544.as_slice());
+
+        buf.to_vec()
+    }
+}
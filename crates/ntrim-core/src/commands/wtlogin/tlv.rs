@@ -50,6 +50,95 @@ pub fn t18(buf: &mut BytesMut, uin: u32) {
     )
 }
 
+pub fn t16(
+    buf: &mut BytesMut,
+    sso_version: u32,
+    sub_app_id: u32,
+    guid: &[u8],
+    apk_id: &str,
+    apk_ver: &str,
+    apk_sign: &[u8],
+) {
+    tlv_builder(buf, 0x16, &|w| {
+            w.put_u32(sso_version);
+            w.put_u32(16);
+            w.put_u32(sub_app_id);
+            w.put_slice(guid);
+            w.put_bytes_with_flags(apk_id.as_bytes(), PacketFlag::I16Len);
+            w.put_bytes_with_flags(apk_ver.as_bytes(), PacketFlag::I16Len);
+            w.put_bytes_with_flags(apk_sign, PacketFlag::I16Len);
+        },
+    )
+}
+
+/// 二维码图片参数
+pub fn t1b(buf: &mut BytesMut, size: u32, margin: u32, dpi: u32, ec_level: u32) {
+    tlv_builder(buf, 0x1b, &|w| {
+            w.put_u32(0); // micro
+            w.put_u32(0); // version
+            w.put_u32(size);
+            w.put_u32(margin);
+            w.put_u32(dpi);
+            w.put_u32(ec_level);
+            w.put_u32(2); // hint
+            w.put_u16(0);
+        },
+    )
+}
+
+pub fn t1d(buf: &mut BytesMut, misc_bitmap: u32) {
+    tlv_builder(buf, 0x1d, &|w| {
+            w.put_u8(1);
+            w.put_u32(misc_bitmap);
+            w.put_u32(0);
+            w.put_u8(0);
+            w.put_u32(0);
+        },
+    )
+}
+
+pub fn t1f(buf: &mut BytesMut, os_type: &str, os_ver: &str, sim_info: &str, apn: &str) {
+    tlv_builder(buf, 0x1f, &|w| {
+            w.put_u8(0); // is root
+            w.put_bytes_with_flags(os_type.as_bytes(), PacketFlag::I16Len);
+            w.put_bytes_with_flags(os_ver.as_bytes(), PacketFlag::I16Len);
+            w.put_u16(2); // network type: wifi
+            w.put_bytes_with_flags(sim_info.as_bytes(), PacketFlag::I16Len);
+            w.put_u16(0);
+            w.put_bytes_with_flags(apn.as_bytes(), PacketFlag::I16Len);
+        },
+    )
+}
+
+pub fn t33(buf: &mut BytesMut, guid: &[u8]) {
+    tlv_builder(buf, 0x33, &|w| {
+            w.put_slice(guid);
+        },
+    )
+}
+
+pub fn t35(buf: &mut BytesMut, pt_os_version: u32) {
+    tlv_builder(buf, 0x35, &|w| {
+            w.put_u32(pt_os_version);
+        },
+    )
+}
+
+/// 请求返回二维码链接，便于在终端渲染
+pub fn td1(buf: &mut BytesMut, os: &str, device_name: &str) {
+    tlv_builder(buf, 0xd1, &|w| {
+            let req = crate::pb::wtlogin::QrCodeD1Req {
+                sys: Some(crate::pb::wtlogin::qr_code_d1_req::SysInfo {
+                    os: os.to_string(),
+                    device_name: device_name.to_string(),
+                }),
+                r#type: vec![0x30, 0x01],
+            };
+            w.put_slice(req.encode_to_vec().as_slice());
+        },
+    )
+}
+
 pub fn t100(
     buf: &mut BytesMut,
     sso_version: u32,
@@ -68,6 +157,54 @@ pub fn t100(
     )
 }
 
+/// 验证上下文，服务器在验证码、短信等验证步骤中下发，后续请求原样带回
+pub fn t104(buf: &mut BytesMut, t104: &[u8]) {
+    tlv_builder(buf, 0x104, &|w| {
+            w.put_slice(t104);
+        },
+    )
+}
+
+pub fn t106(
+    buf: &mut BytesMut,
+    uin: u32,
+    sso_version: u32,
+    sub_app_id: u32,
+    password_md5: &[u8],
+    guid: &[u8],
+    tgtgt_key: &[u8],
+) {
+    tlv_builder(buf, 0x106, &|w| {
+            let mut body = BytesMut::new();
+            body.put_u16(4); // tgtgt version
+            body.put_u32(rand::random());
+            body.put_u32(sso_version);
+            body.put_u32(16);
+            body.put_u32(0); // App client version
+            body.put_u64(uin as u64);
+            body.put_u32(UNIX_EPOCH.elapsed().unwrap().as_secs() as u32);
+            body.put_u32(0); // fake ip
+            body.put_u8(1); // save password
+            body.put_slice(password_md5);
+            body.put_slice(tgtgt_key);
+            body.put_u32(0);
+            body.put_u8(1); // guid available
+            body.put_slice(guid);
+            body.put_u32(sub_app_id);
+            body.put_u32(1); // password login
+            body.put_bytes_with_flags(uin.to_string().as_bytes(), PacketFlag::I16Len);
+            body.put_u16(0);
+
+            let mut key = BytesMut::new();
+            key.put_slice(password_md5);
+            key.put_u32(0);
+            key.put_u32(uin);
+            let key = md5::compute(key.as_ref());
+            w.put_slice(qqtea_encrypt(body.as_ref(), key.as_slice()).as_slice());
+        },
+    )
+}
+
 pub fn t106_data(buf: &mut BytesMut, en_a1: &[u8]) {
     tlv_builder(buf, 0x106, &|w| {
         w.put_slice(en_a1);
@@ -96,6 +233,20 @@ pub fn t109(buf: &mut BytesMut, android_id: &str) {
     })
 }
 
+pub fn t112(buf: &mut BytesMut, name: &str) {
+    tlv_builder(buf, 0x112, &|w| {
+            w.put_slice(name.as_bytes());
+        },
+    )
+}
+
+pub fn t10a(buf: &mut BytesMut, a2: &[u8]) {
+    tlv_builder(buf, 0x10a, &|w| {
+            w.put_slice(a2);
+        },
+    )
+}
+
 pub fn t116(buf: &mut BytesMut, misc_bitmap: u32, sub_sig_map: u32) {
     tlv_builder(buf, 0x116, &|w| {
             w.put_u8(0x00);
@@ -195,6 +346,13 @@ pub fn t144(
     )
 }
 
+pub fn t143(buf: &mut BytesMut, d2: &[u8]) {
+    tlv_builder(buf, 0x143, &|w| {
+            w.put_slice(d2);
+        },
+    )
+}
+
 pub fn t145(buf: &mut BytesMut, guid: &[u8]) {
     tlv_builder(buf, 0x145, &|w| {
             w.put_slice(guid);
@@ -232,6 +390,27 @@ pub fn t16e(buf: &mut BytesMut, model: &str) {
     )
 }
 
+pub fn t174(buf: &mut BytesMut, t174: &[u8]) {
+    tlv_builder(buf, 0x174, &|w| {
+            w.put_slice(t174);
+        },
+    )
+}
+
+pub fn t17a(buf: &mut BytesMut, sms_app_id: u32) {
+    tlv_builder(buf, 0x17a, &|w| {
+            w.put_u32(sms_app_id);
+        },
+    )
+}
+
+pub fn t17c(buf: &mut BytesMut, sms_code: &str) {
+    tlv_builder(buf, 0x17c, &|w| {
+            w.put_bytes_with_flags(sms_code.as_bytes(), PacketFlag::I16Len);
+        },
+    )
+}
+
 pub fn t177(buf: &mut BytesMut, build_time: u32, sdk_version: &str) {
     tlv_builder(buf, 0x177, &|w| {
             w.put_u8(0x01);
@@ -255,6 +434,64 @@ pub fn t188(buf: &mut BytesMut, android_id: &str) {
     )
 }
 
+pub fn t191(buf: &mut BytesMut, can_web_verify: u8) {
+    tlv_builder(buf, 0x191, &|w| {
+            w.put_u8(can_web_verify);
+        },
+    )
+}
+
+pub fn t193(buf: &mut BytesMut, ticket: &str) {
+    tlv_builder(buf, 0x193, &|w| {
+            w.put_slice(ticket.as_bytes());
+        },
+    )
+}
+
+pub fn t194(buf: &mut BytesMut, imsi: &str) {
+    tlv_builder(buf, 0x194, &|w| {
+            w.put_slice(md5::compute(imsi.as_bytes()).as_ref());
+        },
+    )
+}
+
+pub fn t197(buf: &mut BytesMut) {
+    tlv_builder(buf, 0x197, &|w| {
+            w.put_u8(0);
+        },
+    )
+}
+
+pub fn t198(buf: &mut BytesMut) {
+    tlv_builder(buf, 0x198, &|w| {
+            w.put_u8(0);
+        },
+    )
+}
+
+pub fn t202(buf: &mut BytesMut, wifi_bssid: &str, wifi_ssid: &str) {
+    tlv_builder(buf, 0x202, &|w| {
+            w.put_bytes_with_flags(md5::compute(wifi_bssid.as_bytes()).as_ref(), PacketFlag::I16Len);
+            w.put_bytes_with_flags(wifi_ssid.as_bytes(), PacketFlag::I16Len);
+        },
+    )
+}
+
+pub fn t318(buf: &mut BytesMut, tgt_qr: &[u8]) {
+    tlv_builder(buf, 0x318, &|w| {
+            w.put_slice(tgt_qr);
+        },
+    )
+}
+
+/// 设备锁校验，md5(guid + t402)
+pub fn t401(buf: &mut BytesMut, guid: &[u8], t402: &[u8]) {
+    tlv_builder(buf, 0x401, &|w| {
+            w.put_slice(md5::compute([guid, t402].concat()).as_ref());
+        },
+    )
+}
+
 pub fn t511(buf: &mut BytesMut, domains: &Vec<String>) {
     tlv_builder(buf, 0x511, &|w| {
         let mut arr2 = Vec::new();
@@ -0,0 +1,282 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::UNIX_EPOCH;
+use anyhow::Error;
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use prost::Message;
+use tokio::time::Duration;
+use ntrim_tools::bytes::{BytePacketBuilder, PacketFlag};
+use ntrim_tools::crypto::qqtea::qqtea_decrypt;
+use crate::await_response;
+use crate::client::packet::packet::{CommandType, UniPacket};
+use crate::client::trpc::TrpcClient;
+use crate::commands::wtlogin::tlv::{*};
+use crate::commands::wtlogin::wtlogin_request::{build_oicq_packet, decode_oicq_packet, ecdh_encrypt_body, ecdh_encrypt_key};
+
+/// 二维码登录(wtlogin.trans_emp)，包结构参考ricq
+#[derive(Debug, Clone)]
+pub struct QrCode {
+    /// 查询扫码状态时使用
+    pub sig: Vec<u8>,
+    /// png图片
+    pub image: Vec<u8>,
+    /// 二维码内容，服务端不一定返回
+    pub url: Option<String>,
+}
+
+/// 扫码确认后的临时凭证，用于wtlogin.login完成登录
+#[derive(Debug, Clone)]
+pub struct QrCodeConfirmed {
+    pub uin: u64,
+    pub tmp_pwd: Vec<u8>,
+    pub tmp_no_pic_sig: Vec<u8>,
+    pub tgt_qr: Vec<u8>,
+    pub tgtgt_key: Vec<u8>,
+}
+
+#[derive(Debug, Clone)]
+pub enum QrCodeState {
+    /// 等待扫码
+    WaitingForScan,
+    /// 已扫码，等待手机确认
+    WaitingForConfirm,
+    Confirmed(QrCodeConfirmed),
+    Expired,
+    Canceled,
+}
+
+/// 获取登录二维码
+pub async fn fetch_qrcode(trpc: &Arc<TrpcClient>) -> Result<QrCode, Error> {
+    let body = {
+        let session = trpc.session.read().await;
+        let protocol = &session.protocol;
+        let device = &session.device;
+        let mut buf = BytesMut::new();
+        buf.put_u16(0);
+        buf.put_u32(16);
+        buf.put_u64(0);
+        buf.put_u8(8);
+        buf.put_bytes_with_flags(&[], PacketFlag::I16Len);
+        buf.put_u16(7);
+        t16(&mut buf, protocol.sso_version, protocol.sub_app_id, &session.guid, &protocol.apk_id, &protocol.apk_ver, &protocol.apk_sign);
+        t1b(&mut buf, 3, 4, 72, 2);
+        t1d(&mut buf, protocol.misc_bitmap);
+        t1f(&mut buf, &device.os_type, &device.os_ver, &device.apn_name, &device.apn);
+        t33(&mut buf, &session.guid);
+        t35(&mut buf, 8);
+        td1(&mut buf, &device.os_name, &device.device_name);
+        buf.to_vec()
+    };
+    let payload = send_trans_emp(trpc, 0x31, body).await?;
+    parse_qrcode(payload)
+}
+
+fn parse_qrcode(mut payload: BytesMut) -> Result<QrCode, Error> {
+    ensure_remaining(&payload, 1, "qrcode code")?;
+    let code = payload.get_u8();
+    if code != 0 {
+        return Err(Error::msg(format!("Failed to fetch qrcode, code: {}", code)));
+    }
+    ensure_remaining(&payload, 2, "qrcode sig length")?;
+    let sig_len = payload.get_u16() as usize;
+    ensure_remaining(&payload, sig_len, "qrcode sig")?;
+    let sig = payload.copy_to_bytes(sig_len).to_vec();
+    let tlv_map = read_tlv_map(&mut payload);
+    let image = tlv_map.get(&0x17)
+        .ok_or(Error::msg("Failed to fetch qrcode: tlv_t17 not found"))?
+        .to_vec();
+    let url = tlv_map.get(&0xd1)
+        .and_then(|v| crate::pb::wtlogin::QrCodeD1Rsp::decode(v.as_ref()).ok())
+        .map(|rsp| rsp.url)
+        .filter(|url| !url.is_empty());
+    Ok(QrCode { sig, image, url })
+}
+
+/// 查询二维码状态，建议每隔数秒轮询一次
+pub async fn query_qrcode_state(trpc: &Arc<TrpcClient>, sig: &[u8]) -> Result<QrCodeState, Error> {
+    let mut buf = BytesMut::new();
+    buf.put_u16(5);
+    buf.put_u8(1);
+    buf.put_u32(8);
+    buf.put_u32(16);
+    buf.put_bytes_with_flags(sig, PacketFlag::I16Len);
+    buf.put_u64(0);
+    buf.put_u8(8);
+    buf.put_bytes_with_flags(&[], PacketFlag::I16Len);
+    buf.put_u16(0);
+    let payload = send_trans_emp(trpc, 0x12, buf.to_vec()).await?;
+    parse_qrcode_state(payload)
+}
+
+fn parse_qrcode_state(mut payload: BytesMut) -> Result<QrCodeState, Error> {
+    ensure_remaining(&payload, 2, "qrcode state head length")?;
+    let mut len = payload.get_u16() as usize;
+    if len != 0 {
+        ensure_remaining(&payload, len, "qrcode state head")?;
+        len -= 1;
+        if payload.get_u8() == 2 {
+            len = len.checked_sub(8)
+                .ok_or(Error::msg("Invalid trans_emp response: qrcode state head is too short"))?;
+            payload.advance(8);
+        }
+        payload.advance(len);
+    }
+    ensure_remaining(&payload, 4 + 1, "qrcode state code")?;
+    payload.advance(4);
+    let code = payload.get_u8();
+    match code {
+        0 => {}
+        0x30 => return Ok(QrCodeState::WaitingForScan),
+        0x35 => return Ok(QrCodeState::WaitingForConfirm),
+        0x36 => return Ok(QrCodeState::Canceled),
+        0x11 => return Ok(QrCodeState::Expired),
+        _ => return Err(Error::msg(format!("Unknown qrcode state: 0x{:x}", code))),
+    }
+    ensure_remaining(&payload, 8 + 4, "qrcode confirmed uin")?;
+    let uin = payload.get_u64();
+    payload.advance(4);
+    let tlv_map = read_tlv_map(&mut payload);
+    let get_tlv = |ver: u16| tlv_map.get(&ver)
+        .map(|v| v.to_vec())
+        .ok_or(Error::msg(format!("Qrcode confirmed but tlv_t{:x} not found", ver)));
+    Ok(QrCodeState::Confirmed(QrCodeConfirmed {
+        uin,
+        tmp_pwd: get_tlv(0x18)?,
+        tmp_no_pic_sig: get_tlv(0x19)?,
+        tgt_qr: get_tlv(0x65)?,
+        tgtgt_key: get_tlv(0x1e)?,
+    }))
+}
+
+/// 发送trans_emp请求，返回去掉code2d包头后的内容
+async fn send_trans_emp(trpc: &Arc<TrpcClient>, cmd: u16, body: Vec<u8>) -> Result<BytesMut, Error> {
+    let session = trpc.session.read().await;
+    let seq = session.next_seq();
+    let code2d = build_code2d_packet(seq, cmd, body.as_slice());
+    let packet = build_oicq_packet(
+        0, 0x812,
+        ecdh_encrypt_key(&session),
+        ecdh_encrypt_body(&session).as_slice(),
+        code2d.as_slice()
+    );
+    let share_key = session.ecdh.share_key.clone();
+    drop(session);
+    let uni_packet = UniPacket::new(CommandType::Login, "wtlogin.trans_emp".to_string(), packet);
+
+    let msg = await_response!(Duration::from_secs(15), async {
+        match trpc.send_uni_packet_with_seq(uni_packet, seq).await {
+            Some(rx) => rx.await.map_err(|e| Error::new(e)),
+            None => Err(Error::msg("Tcp connection exception")),
+        }
+    }, |value| {
+        Ok(value)
+    }, |e| {
+        Err(e)
+    })?;
+    let (_, result, body) = decode_oicq_packet(msg.wup_buffer.as_slice());
+    if result != 0 {
+        return Err(Error::msg(format!("wtlogin.trans_emp failed, result: 0x{:x}", result)));
+    }
+    let body = qqtea_decrypt(body.as_slice(), share_key.as_slice())
+        .ok_or(Error::msg("Failed to decrypt trans_emp response"))?;
+    if body.len() < 48 {
+        return Err(Error::msg("Invalid trans_emp response"));
+    }
+    let mut payload = BytesMut::from(body.as_slice());
+    payload.advance(5); // trans_emp head
+    payload.advance(1 + 2);
+    let rsp_cmd = payload.get_u16();
+    if rsp_cmd != cmd {
+        return Err(Error::msg(format!("Unexpected trans_emp response cmd: 0x{:x}", rsp_cmd)));
+    }
+    payload.advance(21 + 1 + 2 + 2 + 4 + 8);
+    Ok(payload)
+}
+
+/// 服务器返回的数据不足时返回错误，而不是让`Buf`读取越界panic
+fn ensure_remaining(buf: &BytesMut, len: usize, field: &str) -> Result<(), Error> {
+    if buf.remaining() < len {
+        return Err(Error::msg(format!(
+            "Invalid trans_emp response: {} needs {} bytes, {} left", field, len, buf.remaining()
+        )));
+    }
+    Ok(())
+}
+
+fn build_code2d_packet(seq: u32, cmd: u16, body: &[u8]) -> Vec<u8> {
+    let mut buf = BytesMut::new();
+    buf.put_u8(0);
+    buf.put_u16(body.len() as u16 + 53);
+    buf.put_u32(16); // app id
+    buf.put_u32(0x72); // role
+    buf.put_slice(&[0u8; 3]);
+    buf.put_u32(UNIX_EPOCH.elapsed().unwrap().as_secs() as u32);
+    buf.put_u8(2);
+    buf.put_u16(43 + body.len() as u16 + 1);
+    buf.put_u16(cmd);
+    buf.put_slice(&[0u8; 21]);
+    buf.put_u8(3);
+    buf.put_u16(0);
+    buf.put_u16(50); // version
+    buf.put_u32(seq);
+    buf.put_u64(0);
+    buf.put_slice(body);
+    buf.put_u8(3);
+    buf.to_vec()
+}
+
+fn read_tlv_map(buf: &mut BytesMut) -> HashMap<u16, Bytes> {
+    let mut tlv_map = HashMap::new();
+    if buf.remaining() < 2 {
+        return tlv_map;
+    }
+    let tlv_cnt = buf.get_u16();
+    for _ in 0..tlv_cnt {
+        if buf.remaining() < 4 { break }
+        let tlv_type = buf.get_u16();
+        let tlv_len = buf.get_u16() as usize;
+        if buf.remaining() < tlv_len { break }
+        tlv_map.insert(tlv_type, buf.copy_to_bytes(tlv_len));
+    }
+    tlv_map
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payload(data: &[u8]) -> BytesMut {
+        BytesMut::from(data)
+    }
+
+    #[test]
+    fn truncated_qrcode_is_error() {
+        assert!(parse_qrcode(payload(&[])).is_err());
+        assert!(parse_qrcode(payload(&[0])).is_err());
+        assert!(parse_qrcode(payload(&[0, 0, 4, 1, 2])).is_err());
+
+        let mut data = vec![0, 0, 2, 0xaa, 0xbb];
+        data.extend([0, 1, 0, 0x17, 0, 2, 0x89, 0x50]);
+        let qrcode = parse_qrcode(payload(&data)).unwrap();
+        assert_eq!(qrcode.sig, vec![0xaa, 0xbb]);
+        assert_eq!(qrcode.image, vec![0x89, 0x50]);
+    }
+
+    #[test]
+    fn truncated_qrcode_state_is_error() {
+        assert!(parse_qrcode_state(payload(&[])).is_err());
+        // 头部长度大于剩余数据
+        assert!(parse_qrcode_state(payload(&[0, 9, 2])).is_err());
+        // 头部类型为2时长度不足以跳过8字节
+        assert!(parse_qrcode_state(payload(&[0, 3, 2, 0, 0])).is_err());
+        // 缺少状态码
+        assert!(parse_qrcode_state(payload(&[0, 0, 0, 0, 0])).is_err());
+        // 已确认但缺少uin
+        assert!(parse_qrcode_state(payload(&[0, 0, 0, 0, 0, 0, 0, 1, 2])).is_err());
+
+        let state = parse_qrcode_state(payload(&[0, 1, 1, 0, 0, 0, 0, 0x30])).unwrap();
+        assert!(matches!(state, QrCodeState::WaitingForScan));
+        let state = parse_qrcode_state(payload(&[0, 9, 2, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x35])).unwrap();
+        assert!(matches!(state, QrCodeState::WaitingForConfirm));
+    }
+}
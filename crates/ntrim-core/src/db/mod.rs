@@ -1,10 +1,12 @@
 pub mod simple_record;
+pub mod uid_record;
 
 use std::sync::OnceLock;
 use sqlx::{Acquire, PgPool};
 use sqlx::postgres::PgPoolOptions;
 use ntrim_tools::tokiort::global_tokio_runtime;
 pub use crate::db::simple_record::SimpleMessageRecord;
+pub use crate::db::uid_record::UidRecord;
 
 pub static PG_POOL: OnceLock<PgPool> = OnceLock::new();
 
@@ -28,6 +30,7 @@ pub async fn ensure_table_exists() -> Result<(), anyhow::Error> {
     let pool = PG_POOL.get().unwrap();
     let result = tokio::try_join!(
         SimpleMessageRecord::create_table(pool),
+        UidRecord::create_table(pool),
 
     )?;
 
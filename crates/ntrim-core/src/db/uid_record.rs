@@ -0,0 +1,66 @@
+use anyhow::Error;
+use chrono::NaiveDateTime;
+use sqlx::{FromRow, PgPool};
+
+#[derive(Debug, FromRow)]
+pub struct UidRecord {
+    pub uin: i64,
+    pub uid: String,
+    pub update_time: NaiveDateTime,
+}
+
+const TABLE_NAME: &'static str = "uin_uid_mappings";
+
+impl UidRecord {
+    pub async fn create_table(pool: &PgPool) -> Result<(), Error> {
+        let exists: (bool,) = sqlx::query_as(format!("SELECT EXISTS ( \
+            SELECT 1 \
+            FROM information_schema.tables \
+            WHERE table_schema = 'public' AND TABLE_NAME = '{}' \
+        )", TABLE_NAME).as_str()).fetch_one(pool).await?;
+        if !exists.0 {
+            sqlx::query(format!("CREATE TABLE {} ( \
+                uin BIGINT PRIMARY KEY, \
+                uid VARCHAR(64) NOT NULL, \
+                update_time TIMESTAMP NOT NULL \
+            )", TABLE_NAME).as_str()).execute(pool).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn insert(pool: &PgPool, record: UidRecord) -> Result<(), Error> {
+        sqlx::query(format!(r#"
+            INSERT INTO "public"."{}" ("uin", "uid", "update_time")
+            VALUES ($1, $2, $3)
+            ON CONFLICT ("uin") DO UPDATE SET
+                "uid" = EXCLUDED."uid",
+                "update_time" = EXCLUDED."update_time"
+        "#, TABLE_NAME).as_str())
+            .bind(record.uin)
+            .bind(&record.uid)
+            .bind(record.update_time)
+            .execute(pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn get_by_uin(pool: &PgPool, uin: i64) -> Result<Option<UidRecord>, Error> {
+        let record = sqlx::query_as::<_, UidRecord>(format!(
+            "SELECT uin, uid, update_time FROM {} WHERE uin = $1", TABLE_NAME
+        ).as_str())
+            .bind(uin)
+            .fetch_optional(pool)
+            .await?;
+        Ok(record)
+    }
+
+    pub async fn get_by_uid(pool: &PgPool, uid: &str) -> Result<Option<UidRecord>, Error> {
+        let record = sqlx::query_as::<_, UidRecord>(format!(
+            "SELECT uin, uid, update_time FROM {} WHERE uid = $1", TABLE_NAME
+        ).as_str())
+            .bind(uid)
+            .fetch_optional(pool)
+            .await?;
+        Ok(record)
+    }
+}
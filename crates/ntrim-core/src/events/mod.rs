@@ -1 +1,13 @@
-pub mod wtlogin_event;
\ No newline at end of file
+pub mod wtlogin_event;
+pub mod notice_event;
+pub mod request_event;
+
+use crate::events::notice_event::NoticeEvent;
+use crate::events::request_event::RequestEvent;
+
+/// 由推送解析出的事件，通过`Bot::subscribe`订阅
+#[derive(Debug, Clone)]
+pub enum BotEvent {
+    Notice(NoticeEvent),
+    Request(RequestEvent),
+}
@@ -0,0 +1,104 @@
+use ntrim_tools::cqp::CQCode;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemberIncreaseType {
+    /// 管理员同意入群
+    Approve,
+    /// 成员邀请入群
+    Invite,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemberDecreaseType {
+    /// 主动退群
+    Leave,
+    /// 成员被踢出
+    Kick,
+    /// 自己被踢出
+    KickMe,
+}
+
+/// 离线文件(私聊文件)
+#[derive(Debug, Clone)]
+pub struct OfflineFile {
+    pub file_uuid: String,
+    pub file_hash: String,
+    pub file_name: String,
+    pub file_size: u64,
+    pub file_md5: String,
+    /// 过期时间戳
+    pub expire_time: u64,
+}
+
+impl OfflineFile {
+    /// 消息中的`[CQ:file]`段，`file_id`与`file_hash`用于获取下载地址
+    pub fn to_cq_code(&self) -> CQCode {
+        CQCode::Special {
+            cq_type: "file".to_string(),
+            params: [
+                ("file_id", self.file_uuid.clone()),
+                ("file_hash", self.file_hash.clone()),
+                ("name", self.file_name.clone()),
+                ("size", self.file_size.to_string()),
+                ("md5", self.file_md5.clone()),
+                ("expire", self.expire_time.to_string()),
+            ].into_iter().map(|(k, v)| (k.to_string(), v)).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum NoticeEvent {
+    /// 群成员增加
+    GroupMemberIncrease {
+        group_id: u64,
+        user_id: u64,
+        operator_id: u64,
+        sub_type: MemberIncreaseType,
+    },
+    /// 群成员减少
+    GroupMemberDecrease {
+        group_id: u64,
+        user_id: u64,
+        operator_id: u64,
+        sub_type: MemberDecreaseType,
+    },
+    /// 群管理员变动
+    GroupAdminChange {
+        group_id: u64,
+        user_id: u64,
+        is_admin: bool,
+    },
+    /// 群名片变更
+    GroupCardChange {
+        group_id: u64,
+        user_id: u64,
+        card_new: String,
+        card_old: String,
+    },
+    /// 群消息被贴表情或取消
+    GroupReaction {
+        group_id: u64,
+        msg_seq: u32,
+        user_id: u64,
+        /// 表情id
+        code: String,
+        /// 该表情当前的数量
+        count: u32,
+        is_add: bool,
+    },
+    /// 戳一戳，私聊时`group_id`为空
+    Poke {
+        group_id: Option<u64>,
+        user_id: u64,
+        target_id: u64,
+        /// 动作文本，例如"戳了戳"
+        action: String,
+        suffix: String,
+    },
+    /// 收到离线文件
+    OfflineFile {
+        user_id: u64,
+        file: OfflineFile,
+    },
+}
@@ -0,0 +1,57 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// 群请求的唯一标识，处理请求时原样传回
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GroupRequestFlag {
+    pub group_id: u64,
+    pub seq: u64,
+    pub event_type: u32,
+}
+
+impl fmt::Display for GroupRequestFlag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.group_id, self.seq, self.event_type)
+    }
+}
+
+impl FromStr for GroupRequestFlag {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split(':').map(|p| p.parse::<u64>());
+        match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(Ok(group_id)), Some(Ok(seq)), Some(Ok(event_type)), None) => Ok(Self {
+                group_id,
+                seq,
+                event_type: event_type as u32,
+            }),
+            _ => Err(anyhow::Error::msg(format!("Invalid group request flag: {}", s))),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum RequestEvent {
+    /// 加群申请
+    GroupJoin {
+        group_id: u64,
+        user_id: u64,
+        comment: String,
+        flag: GroupRequestFlag,
+    },
+    /// 邀请自己入群
+    GroupInvite {
+        group_id: u64,
+        /// 邀请人
+        user_id: u64,
+        flag: GroupRequestFlag,
+    },
+    /// 好友申请，`flag`为申请人的uid
+    Friend {
+        user_id: u64,
+        comment: String,
+        source: String,
+        flag: String,
+    },
+}
@@ -1,4 +1,5 @@
-
+use bytes::{Buf, BytesMut};
+use thiserror::Error;
 
 #[derive(Debug)]
 pub enum WtloginResponse {
@@ -8,4 +9,93 @@ pub enum WtloginResponse {
     Fail(anyhow::Error),
     /// Refresh Sig Success.
     RefreshSigSuccess,
-}
\ No newline at end of file
+    /// 需要滑块验证，完成后提交ticket继续登录
+    NeedCaptcha {
+        url: String,
+        ctx: WtloginContext,
+    },
+    /// 设备锁，需要短信验证；没有绑定手机时只能前往verify_url验证
+    DeviceLocked {
+        phone: Option<String>,
+        verify_url: Option<String>,
+        message: String,
+        ctx: WtloginContext,
+    },
+    /// 短信验证码已发送，手机号登录时提交验证码继续登录
+    SmsCodeSent {
+        ctx: WtloginContext,
+    },
+    /// 设备锁无需验证，直接发送确认即可继续登录
+    DeviceLockLogin {
+        ctx: WtloginContext,
+    },
+}
+
+/// 登录验证过程中需要回传给服务器的数据
+#[derive(Debug, Clone, Default)]
+pub struct WtloginContext {
+    pub t104: Vec<u8>,
+    pub t174: Vec<u8>,
+    pub t402: Vec<u8>,
+}
+
+/// 登录失败的原因，由wtlogin的result与tlv_t146解析而来
+#[derive(Error, Debug)]
+pub enum WtloginError {
+    /// 账号或密码错误
+    #[error("Wrong password: {0}")]
+    WrongPassword(String),
+    /// 账号被冻结
+    #[error("Account frozen: {0}")]
+    AccountFrozen(String),
+    /// 短信验证码请求过于频繁
+    #[error("Too many sms requests: {0}")]
+    TooManySmsRequest(String),
+    /// 协议版本过低，需要更新协议信息
+    #[error("Protocol outdated: {0}")]
+    ProtocolOutdated(String),
+    /// 当前设备或网络环境存在风险，被服务器拒绝
+    #[error("Login restricted: {0}")]
+    Restricted(String),
+    /// 其他错误，title与message来自tlv_t146
+    #[error("Wtlogin failed, result: 0x{result:x}, {title}: {message}")]
+    Other {
+        result: u8,
+        title: String,
+        message: String,
+    },
+}
+
+impl WtloginError {
+    pub(crate) fn from_t146(result: u8, t146: Option<&[u8]>) -> Self {
+        let (title, message) = t146.map_or_else(
+            || (String::new(), "Unknown Error".to_string()),
+            |t146| Self::parse_t146(t146)
+        );
+        match result {
+            1 => WtloginError::WrongPassword(message),
+            40 => WtloginError::AccountFrozen(message),
+            162 => WtloginError::TooManySmsRequest(message),
+            235 => WtloginError::ProtocolOutdated(message),
+            237 | 0x9a => WtloginError::Restricted(message),
+            _ => WtloginError::Other { result, title, message }
+        }
+    }
+
+    /// ver(2) code(2) title message
+    pub(crate) fn parse_t146(t146: &[u8]) -> (String, String) {
+        let mut t146 = BytesMut::from(t146);
+        if t146.remaining() < 6 {
+            return (String::new(), String::new());
+        }
+        t146.advance(4);
+        let mut read_str = || {
+            if t146.remaining() < 2 { return String::new() }
+            let len = (t146.get_u16() as usize).min(t146.remaining());
+            String::from_utf8_lossy(t146.copy_to_bytes(len).as_ref()).to_string()
+        };
+        let title = read_str();
+        let message = read_str();
+        (title, message)
+    }
+}
@@ -2,6 +2,8 @@ use std::sync::{Arc, OnceLock};
 use chrono::Local;
 use log::{error, info, warn};
 use crate::bot::Bot;
+use crate::commands::wtlogin::refresh_sig::RefreshSig;
+use crate::commands::wtlogin::refresh_st::RefreshSt;
 use crate::commands::wtlogin::wtlogin_request::{WtloginBuilder, WtloginFactory};
 use crate::events::wtlogin_event::WtloginResponse;
 use crate::session::ticket::{SigType, TicketManager};
@@ -10,7 +12,10 @@ impl Bot {
     pub(crate) async fn auto_refresh_session(self: &Arc<Self>) {
         let bot = Arc::clone(self);
         let session = bot.client.session.read().await;
-        let d2 = session.ticket(SigType::D2).unwrap();
+        // 还没有登录的会话(例如扫码登录)没有D2
+        let Some(d2) = session.ticket(SigType::D2) else {
+            return;
+        };
         if d2.expire_time <= 0 {
             return;
         }
@@ -27,7 +32,7 @@ impl Bot {
                 if interval > 0 {
                     tokio::time::sleep(tokio::time::Duration::from_secs(interval as u64)).await;
                 }
-                if !refresh_sig(&bot).await {
+                if !refresh_tickets(&bot).await {
                     fail_time += 1;
                     if fail_time == 36 {
                         warn!("Auto refresh sig failed! No more automatic session refreshes!");
@@ -48,9 +53,45 @@ impl Bot {
     }
 }
 
+/// WT会话的有效期，超过后RefreshSig无法使用，需要RefreshSt
+pub const WT_SESSION_VALID_TIME: u64 = 30 * 24 * 60 * 60;
+
+/// WT会话过期时使用RefreshSt，否则使用RefreshSig
+pub async fn refresh_tickets(bot: &Arc<Bot>) -> bool {
+    let create_time = bot.client.session.read().await.wt_session_create_time;
+    let elapsed = (Local::now().timestamp() as u64).saturating_sub(create_time);
+    if elapsed >= WT_SESSION_VALID_TIME {
+        info!("WT session is too old ({} days), refreshing st", elapsed / (60 * 60 * 24));
+        refresh_st(bot).await
+    } else {
+        refresh_sig(bot).await
+    }
+}
+
+pub async fn refresh_st(bot: &Arc<Bot>) -> bool {
+    let domains = refresh_pskey_domains().clone();
+    let rx = WtloginBuilder::<RefreshSt>::build(bot.client.clone(), domains)
+        .send().await;
+    match rx.await.unwrap() {
+        WtloginResponse::Success() => {
+            info!("Refresh st success");
+            bot.client.session.read().await.mark_changed();
+            true
+        }
+        WtloginResponse::Fail(e) => {
+            error!("Refresh st failed: {:?}", e);
+            false
+        }
+        resp => {
+            error!("Refresh st failed, unexpected response: {:?}", resp);
+            false
+        }
+    }
+}
+
 pub async fn refresh_sig(bot: &Arc<Bot>) -> bool {
     let domains = refresh_pskey_domains().clone();
-    let rx = WtloginBuilder::build(bot.client.clone(), (16, domains))
+    let rx = WtloginBuilder::<RefreshSig>::build(bot.client.clone(), (16, domains))
         .send().await;
     match rx.await.unwrap() {
         WtloginResponse::Fail(e) => {
@@ -59,12 +100,17 @@ pub async fn refresh_sig(bot: &Arc<Bot>) -> bool {
         }
         WtloginResponse::Success() | WtloginResponse::RefreshSigSuccess => {
             info!("Refresh sig success");
+            bot.client.session.read().await.mark_changed();
             true
         }
+        resp => {
+            error!("Refresh sig failed, unexpected response: {:?}", resp);
+            false
+        }
     }
 }
 
-fn refresh_pskey_domains() -> &'static Vec<String> {
+pub fn refresh_pskey_domains() -> &'static Vec<String> {
     static DOMAINS: OnceLock<Vec<String>> = OnceLock::new();
     DOMAINS.get_or_init(|| {
         vec![
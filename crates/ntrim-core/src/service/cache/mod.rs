@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use anyhow::Error;
+use log::debug;
+use once_cell::sync::Lazy;
+use crate::bot::Bot;
+use crate::service::{friend, group, resolver};
+use crate::service::friend::FriendList;
+use crate::service::group::{GroupInfo, GroupMemberInfo, GroupMemberRole};
+
+/// 群、群成员、好友资料缓存，过期后重新拉取，
+/// 成员变动、名片变更、管理员变动等推送会直接修补缓存
+///
+/// 每个Bot各自持有，不同账号的好友与群成员互不影响
+#[derive(Default)]
+pub(crate) struct MetadataCache(Mutex<CacheData>);
+
+#[derive(Default)]
+struct CacheData {
+    groups: Option<Cached<HashMap<u64, GroupInfo>>>,
+    members: HashMap<u64, GroupMembers>,
+    friends: Option<Cached<FriendList>>,
+}
+
+#[derive(Default)]
+struct GroupMembers {
+    /// 完整成员列表的过期时间，为空表示缓存的成员不完整
+    complete_until: Option<Instant>,
+    members: HashMap<u64, Cached<GroupMemberInfo>>,
+}
+
+struct Cached<T> {
+    value: T,
+    expire_at: Instant,
+}
+
+impl<T> Cached<T> {
+    fn new(value: T) -> Self {
+        Self { value, expire_at: Instant::now() + *CACHE_TTL }
+    }
+
+    fn is_fresh(&self) -> bool {
+        Instant::now() < self.expire_at
+    }
+}
+
+static CACHE_TTL: Lazy<Duration> = Lazy::new(|| {
+    Duration::from_secs(option_env!("METADATA_CACHE_TTL")
+        .map_or(1800, |v| v.parse::<u64>().unwrap_or(1800)))
+});
+
+/// 获取群列表，`no_cache`为真时强制从服务器拉取
+pub async fn group_list(bot: &Arc<Bot>, no_cache: bool) -> Result<Vec<GroupInfo>, Error> {
+    if !no_cache {
+        let cache = bot.metadata.0.lock().unwrap();
+        if let Some(groups) = cache.groups.as_ref().filter(|g| g.is_fresh()) {
+            return Ok(groups.value.values().cloned().collect());
+        }
+    }
+    let groups = group::get_group_list(bot).await?;
+    bot.metadata.0.lock().unwrap().groups = Some(Cached::new(groups.iter()
+        .map(|g| (g.group_id, g.clone()))
+        .collect()));
+    Ok(groups)
+}
+
+/// 获取单个群的资料
+pub async fn group_info(bot: &Arc<Bot>, group_id: u64, no_cache: bool) -> Result<GroupInfo, Error> {
+    if !no_cache {
+        let cache = bot.metadata.0.lock().unwrap();
+        if let Some(info) = cache.groups.as_ref()
+            .filter(|g| g.is_fresh())
+            .and_then(|g| g.value.get(&group_id)) {
+            return Ok(info.clone());
+        }
+    }
+    group_list(bot, true).await?
+        .into_iter()
+        .find(|g| g.group_id == group_id)
+        .ok_or_else(|| Error::msg(format!("Not in group {}", group_id)))
+}
+
+/// 获取完整的群成员列表
+pub async fn group_member_list(bot: &Arc<Bot>, group_id: u64, no_cache: bool) -> Result<Vec<GroupMemberInfo>, Error> {
+    if !no_cache {
+        let cache = bot.metadata.0.lock().unwrap();
+        if let Some(group) = cache.members.get(&group_id)
+            .filter(|m| m.complete_until.map_or(false, |t| Instant::now() < t)) {
+            return Ok(group.members.values().map(|m| m.value.clone()).collect());
+        }
+    }
+    let members = group::get_group_member_list(bot, group_id).await?;
+    let mut cache = bot.metadata.0.lock().unwrap();
+    let group = cache.members.entry(group_id).or_default();
+    group.complete_until = Some(Instant::now() + *CACHE_TTL);
+    group.members = members.iter()
+        .map(|m| (m.uin, Cached::new(m.clone())))
+        .collect();
+    Ok(members)
+}
+
+/// 获取单个群成员资料，缓存未命中时只拉取该成员
+pub async fn group_member_info(bot: &Arc<Bot>, group_id: u64, uin: u64, no_cache: bool) -> Result<GroupMemberInfo, Error> {
+    if !no_cache {
+        let cache = bot.metadata.0.lock().unwrap();
+        if let Some(member) = cache.members.get(&group_id)
+            .and_then(|g| g.members.get(&uin))
+            .filter(|m| m.is_fresh()) {
+            return Ok(member.value.clone());
+        }
+    }
+    let uid = resolver::resolve_uid(bot, uin).await?;
+    let member = group::get_group_member_info(bot, group_id, &uid).await?;
+    bot.metadata.0.lock().unwrap().members.entry(group_id).or_default()
+        .members.insert(member.uin, Cached::new(member.clone()));
+    Ok(member)
+}
+
+/// 获取好友列表
+pub async fn friend_list(bot: &Arc<Bot>, no_cache: bool) -> Result<FriendList, Error> {
+    if !no_cache {
+        let cache = bot.metadata.0.lock().unwrap();
+        if let Some(friends) = cache.friends.as_ref().filter(|f| f.is_fresh()) {
+            return Ok(friends.value.clone());
+        }
+    }
+    let friends = friend::get_friend_list(bot).await?;
+    bot.metadata.0.lock().unwrap().friends = Some(Cached::new(friends.clone()));
+    Ok(friends)
+}
+
+/// 有新成员入群，没有成员资料可用，只能将成员列表标记为不完整
+pub(crate) fn on_member_increase(bot: &Bot, group_id: u64, uin: u64) {
+    debug!("Member {} joined group {}, patch metadata cache", uin, group_id);
+    let mut cache = bot.metadata.0.lock().unwrap();
+    if let Some(info) = cache.groups.as_mut().and_then(|g| g.value.get_mut(&group_id)) {
+        info.member_count += 1;
+    }
+    if let Some(group) = cache.members.get_mut(&group_id) {
+        group.complete_until = None;
+    }
+}
+
+/// 成员退群或被踢出
+pub(crate) fn on_member_decrease(bot: &Bot, group_id: u64, uin: u64) {
+    debug!("Member {} left group {}, patch metadata cache", uin, group_id);
+    let mut cache = bot.metadata.0.lock().unwrap();
+    if let Some(info) = cache.groups.as_mut().and_then(|g| g.value.get_mut(&group_id)) {
+        info.member_count = info.member_count.saturating_sub(1);
+    }
+    if let Some(group) = cache.members.get_mut(&group_id) {
+        group.members.remove(&uin);
+    }
+}
+
+/// 自己退出或被移出群聊，丢弃该群的全部缓存
+pub(crate) fn on_group_leave(bot: &Bot, group_id: u64) {
+    let mut cache = bot.metadata.0.lock().unwrap();
+    if let Some(groups) = cache.groups.as_mut() {
+        groups.value.remove(&group_id);
+    }
+    cache.members.remove(&group_id);
+}
+
+/// 群名称变更
+pub(crate) fn on_group_rename(bot: &Bot, group_id: u64, group_name: &str) {
+    let mut cache = bot.metadata.0.lock().unwrap();
+    if let Some(info) = cache.groups.as_mut().and_then(|g| g.value.get_mut(&group_id)) {
+        info.group_name = group_name.to_string();
+    }
+}
+
+/// 管理员变动
+pub(crate) fn on_admin_change(bot: &Bot, group_id: u64, uin: u64, is_admin: bool) {
+    let mut cache = bot.metadata.0.lock().unwrap();
+    if let Some(member) = cache.members.get_mut(&group_id).and_then(|g| g.members.get_mut(&uin)) {
+        if member.value.role != GroupMemberRole::Owner {
+            member.value.role = if is_admin { GroupMemberRole::Admin } else { GroupMemberRole::Member };
+        }
+    }
+}
+
+/// 群名片变更，返回缓存中的旧名片
+pub(crate) fn on_card_change(bot: &Bot, group_id: u64, uin: u64, card: &str) -> Option<String> {
+    let mut cache = bot.metadata.0.lock().unwrap();
+    cache.members.get_mut(&group_id)
+        .and_then(|g| g.members.get_mut(&uin))
+        .map(|member| std::mem::replace(&mut member.value.card, card.to_string()))
+}
+
+/// 好友增删，好友列表需要重新拉取
+pub(crate) fn on_friend_change(bot: &Bot) {
+    bot.metadata.0.lock().unwrap().friends = None;
+}
+
+/// 好友备注变更
+pub(crate) fn on_friend_remark(bot: &Bot, uin: u64, remark: &str) {
+    let mut cache = bot.metadata.0.lock().unwrap();
+    if let Some(friend) = cache.friends.as_mut()
+        .and_then(|f| f.value.friends.iter_mut().find(|f| f.uin == uin)) {
+        friend.remark = remark.to_string();
+    }
+}
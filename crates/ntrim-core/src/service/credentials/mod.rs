@@ -0,0 +1,53 @@
+use std::sync::Arc;
+use anyhow::Error;
+use crate::bot::Bot;
+
+#[derive(Debug, Clone)]
+pub struct Credentials {
+    pub cookies: String,
+    pub csrf_token: u64,
+}
+
+/// 计算skey或pskey的`bkn`(即`g_tk`)
+pub fn gtk(key: &str) -> u64 {
+    let mut hash: u64 = 5381;
+    for c in key.bytes() {
+        hash = hash.wrapping_add((hash << 5).wrapping_add(c as u64));
+    }
+    hash & 0x7fffffff
+}
+
+/// 获取指定域名的cookies，`domain`为空时只包含skey
+pub async fn get_cookies(bot: &Arc<Bot>, domain: &str) -> Result<String, Error> {
+    let session = bot.client.session.read().await;
+    if session.skey.is_empty() {
+        return Err(Error::msg("Empty skey, please refresh sig first"));
+    }
+    let mut cookies = format!("uin=o{uin}; skey={skey}; p_uin=o{uin}", uin = session.uin, skey = session.skey);
+    if !domain.is_empty() {
+        let (pskey, pt4token) = session.web_key(domain)
+            .ok_or_else(|| Error::msg(format!("No pskey for domain: {}", domain)))?;
+        cookies.push_str(&format!("; p_skey={}", pskey));
+        if !pt4token.is_empty() {
+            cookies.push_str(&format!("; pt4_token={}", pt4token));
+        }
+    }
+    Ok(cookies)
+}
+
+/// 获取skey对应的csrf token
+pub async fn get_csrf_token(bot: &Arc<Bot>) -> Result<u64, Error> {
+    let session = bot.client.session.read().await;
+    if session.skey.is_empty() {
+        return Err(Error::msg("Empty skey, please refresh sig first"));
+    }
+    Ok(gtk(&session.skey))
+}
+
+/// 同时获取cookies与csrf token
+pub async fn get_credentials(bot: &Arc<Bot>, domain: &str) -> Result<Credentials, Error> {
+    Ok(Credentials {
+        cookies: get_cookies(bot, domain).await?,
+        csrf_token: get_csrf_token(bot).await?,
+    })
+}
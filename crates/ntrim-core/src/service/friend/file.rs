@@ -0,0 +1,16 @@
+use std::sync::Arc;
+use anyhow::Error;
+use crate::await_command;
+use crate::bot::Bot;
+use crate::service::NotFound;
+
+/// 获取离线文件(私聊文件)的下载地址
+pub async fn get_file_url(bot: &Arc<Bot>, file_uuid: &str, file_hash: &str) -> Result<String, Error> {
+    let rsp = await_command!(Bot::request_private_file_download(bot, file_uuid.to_string(), file_hash.to_string()))?;
+    let result = rsp.and_then(|rsp| rsp.body).and_then(|body| body.result)
+        .ok_or_else(|| Error::msg(format!("Failed to fetch download url of {}", file_uuid)))?;
+    match (result.server.filter(|s| !s.is_empty()), result.url.filter(|u| !u.is_empty())) {
+        (Some(server), Some(url)) => Ok(format!("https://{}:{}{}&isthumb=0", server, result.port.unwrap_or(443), url)),
+        _ => Err(NotFound(format!("Download url of {} is empty", file_uuid)).into()),
+    }
+}
@@ -0,0 +1,47 @@
+use std::sync::Arc;
+use anyhow::Error;
+use crate::await_command;
+use crate::bot::Bot;
+use crate::pb::oidb::TrpcOidbResponse;
+use crate::service::{cache, resolver};
+
+fn check(rsp: Option<TrpcOidbResponse>) -> Result<(), Error> {
+    match rsp {
+        Some(rsp) if rsp.result == 0 => Ok(()),
+        Some(rsp) => Err(Error::msg(format!("Server error {}: {}", rsp.result, rsp.error_msg.unwrap_or_default()))),
+        None => Err(Error::msg("Invalid oidb response")),
+    }
+}
+
+/// 同意或拒绝好友申请，`flag`为申请人的uid，同意时可以顺带设置备注
+pub async fn handle_friend_request(bot: &Arc<Bot>, flag: &str, approve: bool, remark: &str) -> Result<(), Error> {
+    check(await_command!(Bot::handle_friend_request(bot, flag.to_string(), approve))?)?;
+    if approve {
+        cache::on_friend_change(bot);
+        if !remark.is_empty() {
+            check(await_command!(Bot::set_friend_remark(bot, flag.to_string(), remark.to_string()))?)?;
+        }
+    }
+    Ok(())
+}
+
+/// 删除好友，`block`为真时同时拉黑
+pub async fn delete_friend(bot: &Arc<Bot>, user_id: u64, block: bool) -> Result<(), Error> {
+    let uid = resolver::resolve_uid(bot, user_id).await?;
+    check(await_command!(Bot::delete_friend(bot, uid, block))?)?;
+    cache::on_friend_change(bot);
+    Ok(())
+}
+
+/// 设置好友备注
+pub async fn set_friend_remark(bot: &Arc<Bot>, user_id: u64, remark: &str) -> Result<(), Error> {
+    let uid = resolver::resolve_uid(bot, user_id).await?;
+    check(await_command!(Bot::set_friend_remark(bot, uid, remark.to_string()))?)?;
+    cache::on_friend_remark(bot, user_id, remark);
+    Ok(())
+}
+
+/// 戳一戳好友
+pub async fn poke(bot: &Arc<Bot>, user_id: u64) -> Result<(), Error> {
+    check(await_command!(Bot::send_poke(bot, user_id, None))?)
+}
@@ -0,0 +1,90 @@
+pub mod manage;
+pub mod file;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use anyhow::Error;
+use crate::await_command;
+use crate::bot::Bot;
+use crate::pb::oidb::cmd0xfd4::Friend;
+use crate::service::resolver;
+
+#[derive(Debug, Clone)]
+pub struct FriendInfo {
+    pub uin: u64,
+    pub uid: String,
+    pub nickname: String,
+    pub remark: String,
+    /// 个性签名
+    pub signature: String,
+    pub category_id: u32,
+}
+
+impl From<Friend> for FriendInfo {
+    fn from(friend: Friend) -> Self {
+        let mut properties = friend.additional.into_iter()
+            .filter(|a| a.r#type == 1)
+            .filter_map(|a| a.layer)
+            .flat_map(|l| l.properties)
+            .map(|p| (p.code, p.value.unwrap_or_default()))
+            .collect::<HashMap<u32, String>>();
+        Self {
+            uin: friend.uin,
+            uid: friend.uid,
+            nickname: properties.remove(&20002).unwrap_or_default(),
+            remark: properties.remove(&103).unwrap_or_default(),
+            signature: properties.remove(&102).unwrap_or_default(),
+            category_id: friend.category_id.unwrap_or(0),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FriendCategory {
+    pub category_id: u32,
+    pub category_name: String,
+    pub friend_count: u32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct FriendList {
+    pub friends: Vec<FriendInfo>,
+    pub categories: Vec<FriendCategory>,
+}
+
+impl FriendList {
+    pub fn category(&self, category_id: u32) -> Option<&FriendCategory> {
+        self.categories.iter().find(|c| c.category_id == category_id)
+    }
+}
+
+/// 拉取完整的好友列表及好友分组，会自动处理分页
+pub async fn get_friend_list(bot: &Arc<Bot>) -> Result<FriendList, Error> {
+    let mut list = FriendList::default();
+    let mut next_uin: Option<u64> = None;
+    loop {
+        let rsp = match await_command!(Bot::fetch_friend_list(bot, next_uin.take()))? {
+            Some(rsp) => rsp,
+            None => return Err(Error::msg("Failed to fetch friend list")),
+        };
+        list.friends.extend(rsp.friends.into_iter().map(|f| {
+            resolver::learn(f.uin, &f.uid);
+            FriendInfo::from(f)
+        }));
+        for category in rsp.categories {
+            if list.category(category.category_id).is_some() {
+                continue;
+            }
+            list.categories.push(FriendCategory {
+                category_id: category.category_id,
+                category_name: category.category_name.unwrap_or_default(),
+                friend_count: category.friend_count.unwrap_or(0),
+            });
+        }
+        match rsp.next {
+            Some(next) => next_uin = Some(next.uin),
+            None => break,
+        }
+    }
+    Ok(list)
+}
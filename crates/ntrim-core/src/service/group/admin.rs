@@ -0,0 +1,78 @@
+use std::sync::Arc;
+use crate::await_command;
+use crate::bot::Bot;
+use crate::service::{cache, NotFound};
+use crate::service::group::{GroupError, GroupInfo, GroupMemberInfo, GroupMemberRole};
+
+/// 获取自己在群内的资料，不在群内时返回`NotInGroup`
+pub(super) async fn self_member(bot: &Arc<Bot>, group_id: u64) -> Result<GroupMemberInfo, GroupError> {
+    let in_group = |groups: Vec<GroupInfo>| groups.iter().any(|g| g.group_id == group_id);
+    // 缓存的群列表可能早于加群时间，未命中时强制刷新一次
+    if !in_group(cache::group_list(bot, false).await?) && !in_group(cache::group_list(bot, true).await?) {
+        return Err(GroupError::NotInGroup(group_id));
+    }
+    let uin = bot.client.session.read().await.uin;
+    Ok(cache::group_member_info(bot, group_id, uin, false).await?)
+}
+
+/// 获取目标成员资料，只有查无此人才视为不在群内，网络错误等原样返回
+pub(super) async fn target_member(bot: &Arc<Bot>, group_id: u64, user_id: u64) -> Result<GroupMemberInfo, GroupError> {
+    cache::group_member_info(bot, group_id, user_id, false).await.map_err(|e| {
+        if e.is::<NotFound>() {
+            GroupError::MemberNotFound { group_id, user_id }
+        } else {
+            GroupError::Other(e)
+        }
+    })
+}
+
+/// 管理员只能操作普通成员，群主可以操作所有人
+pub(super) async fn check_manageable(bot: &Arc<Bot>, group_id: u64, user_id: u64) -> Result<GroupMemberInfo, GroupError> {
+    let operator = self_member(bot, group_id).await?;
+    let target = target_member(bot, group_id, user_id).await?;
+    match (operator.role, target.role) {
+        (GroupMemberRole::Owner, GroupMemberRole::Owner) => Err(GroupError::PermissionDenied("can't operate the owner".to_string())),
+        (GroupMemberRole::Owner, _) => Ok(target),
+        (GroupMemberRole::Admin, GroupMemberRole::Member) => Ok(target),
+        (GroupMemberRole::Admin, _) => Err(GroupError::PermissionDenied("admin can only operate members".to_string())),
+        (GroupMemberRole::Member, _) => Err(GroupError::PermissionDenied("not an admin".to_string())),
+    }
+}
+
+/// 踢出群成员，`reject_add_request`为真时拒绝此人再次加群
+pub async fn kick_member(bot: &Arc<Bot>, group_id: u64, user_id: u64, reject_add_request: bool) -> Result<(), GroupError> {
+    let target = check_manageable(bot, group_id, user_id).await?;
+    let rsp = await_command!(Bot::kick_group_member(bot, group_id, target.uid, reject_add_request, String::new()))?;
+    GroupError::check(rsp)?;
+    Ok(())
+}
+
+/// 禁言群成员`duration`秒，为0时解除禁言
+pub async fn mute_member(bot: &Arc<Bot>, group_id: u64, user_id: u64, duration: u32) -> Result<(), GroupError> {
+    let target = check_manageable(bot, group_id, user_id).await?;
+    let rsp = await_command!(Bot::mute_group_member(bot, group_id, target.uid, duration))?;
+    GroupError::check(rsp)?;
+    Ok(())
+}
+
+/// 开启或关闭全员禁言
+pub async fn mute_whole_group(bot: &Arc<Bot>, group_id: u64, enable: bool) -> Result<(), GroupError> {
+    if self_member(bot, group_id).await?.role == GroupMemberRole::Member {
+        return Err(GroupError::PermissionDenied("not an admin".to_string()));
+    }
+    let rsp = await_command!(Bot::mute_whole_group(bot, group_id, enable))?;
+    GroupError::check(rsp)?;
+    Ok(())
+}
+
+/// 设置或取消群管理员，仅群主可用
+pub async fn set_admin(bot: &Arc<Bot>, group_id: u64, user_id: u64, is_admin: bool) -> Result<(), GroupError> {
+    if self_member(bot, group_id).await?.role != GroupMemberRole::Owner {
+        return Err(GroupError::PermissionDenied("not the owner".to_string()));
+    }
+    let target = target_member(bot, group_id, user_id).await?;
+    let rsp = await_command!(Bot::set_group_admin(bot, group_id, target.uid, is_admin))?;
+    GroupError::check(rsp)?;
+    cache::on_admin_change(bot, group_id, user_id, is_admin);
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use thiserror::Error;
+use crate::pb::oidb::TrpcOidbResponse;
+
+#[derive(Error, Debug)]
+pub enum GroupError {
+    /// 自己不在该群
+    #[error("Not in group {0}")]
+    NotInGroup(u64),
+    /// 目标成员不在该群
+    #[error("Member {user_id} not found in group {group_id}")]
+    MemberNotFound {
+        group_id: u64,
+        user_id: u64,
+    },
+    /// 权限不足，例如管理员操作群主
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
+    /// 服务器返回的其他错误
+    #[error("Server error {code}: {msg}")]
+    Server {
+        code: u32,
+        msg: String,
+    },
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+/// 表示权限不足的错误码，OIDB与群文件等业务共用，群文件的错误码为负数
+const PERMISSION_DENIED_CODES: [i32; 2] = [-403, 403];
+
+impl GroupError {
+    /// 检查OIDB回包的错误码
+    pub(crate) fn check(rsp: Option<TrpcOidbResponse>) -> Result<Vec<u8>, GroupError> {
+        let rsp = rsp.ok_or_else(|| GroupError::Other(anyhow::Error::msg("Invalid oidb response")))?;
+        if rsp.result == 0 {
+            return Ok(rsp.body);
+        }
+        Err(Self::from_code(rsp.result as i32, rsp.error_msg.unwrap_or_default()))
+    }
+
+    /// 检查回包内部的错误码，群文件等业务会将错误码放在body里
+    pub(crate) fn check_ret(ret_code: Option<i32>, msg: Option<String>) -> Result<(), GroupError> {
+        match ret_code {
+            None | Some(0) => Ok(()),
+            Some(code) => Err(Self::from_code(code, msg.unwrap_or_default())),
+        }
+    }
+
+    fn from_code(code: i32, msg: String) -> GroupError {
+        if PERMISSION_DENIED_CODES.contains(&code) {
+            GroupError::PermissionDenied(msg)
+        } else {
+            GroupError::Server { code: code as u32, msg }
+        }
+    }
+}
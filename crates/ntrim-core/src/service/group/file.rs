@@ -0,0 +1,246 @@
+use std::sync::Arc;
+use anyhow::Error;
+use prost::Message;
+use sha1::{Digest, Sha1};
+use crate::await_command;
+use crate::bot::Bot;
+use crate::pb::oidb::cmd0x6d8::{GroupFileInfo, GroupFolderInfo};
+use crate::pb::trpc::highway::{ * };
+use crate::service::group::GroupError;
+use crate::service::highway;
+
+/// 根目录的文件夹id
+pub const ROOT_FOLDER: &str = "/";
+
+/// 每页拉取的文件数量
+const PAGE_SIZE: u32 = 20;
+
+/// 上传时计算`md5_s2`所用的数据长度
+const MD5_S2_SIZE: usize = 10 * 1024 * 1024;
+
+#[derive(Debug, Clone)]
+pub struct GroupFile {
+    pub group_id: u64,
+    pub file_id: String,
+    pub file_name: String,
+    pub bus_id: u32,
+    pub file_size: u64,
+    pub upload_time: u64,
+    /// 过期时间，0为永久
+    pub expire_time: u64,
+    pub modify_time: u64,
+    pub download_times: u32,
+    pub uploader: u64,
+    pub uploader_name: String,
+    pub md5: String,
+    pub sha1: String,
+    pub folder_id: String,
+}
+
+impl GroupFile {
+    fn new(group_id: u64, info: GroupFileInfo) -> Self {
+        Self {
+            group_id,
+            file_id: info.file_id.unwrap_or_default(),
+            file_name: info.file_name.unwrap_or_default(),
+            bus_id: info.bus_id.unwrap_or(102),
+            file_size: info.file_size.unwrap_or(0),
+            upload_time: info.upload_time.unwrap_or(0) as u64,
+            expire_time: info.expire_time.unwrap_or(0) as u64,
+            modify_time: info.modify_time.unwrap_or(0) as u64,
+            download_times: info.download_times.unwrap_or(0),
+            uploader: info.uploader_uin.unwrap_or(0),
+            uploader_name: info.uploader_name.unwrap_or_default(),
+            md5: hex::encode(info.file_md5.unwrap_or_default()),
+            sha1: hex::encode(info.file_sha1.unwrap_or_default()),
+            folder_id: info.parent_folder_id.unwrap_or_else(|| ROOT_FOLDER.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GroupFolder {
+    pub group_id: u64,
+    pub folder_id: String,
+    pub folder_name: String,
+    pub create_time: u64,
+    pub creator: u64,
+    pub creator_name: String,
+    pub total_file_count: u32,
+}
+
+impl GroupFolder {
+    fn new(group_id: u64, info: GroupFolderInfo) -> Self {
+        Self {
+            group_id,
+            folder_id: info.folder_id.unwrap_or_default(),
+            folder_name: info.folder_name.unwrap_or_default(),
+            create_time: info.create_time.unwrap_or(0) as u64,
+            creator: info.creator_uin.unwrap_or(0),
+            creator_name: info.creator_name.unwrap_or_default(),
+            total_file_count: info.total_file_count.unwrap_or(0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GroupFolderContent {
+    pub files: Vec<GroupFile>,
+    pub folders: Vec<GroupFolder>,
+}
+
+/// 分页列出文件夹内容，返回内容以及是否已到末尾
+pub async fn list_folder(
+    bot: &Arc<Bot>,
+    group_id: u64,
+    folder_id: &str,
+    start_index: u32,
+    count: u32
+) -> Result<(GroupFolderContent, bool), GroupError> {
+    let rsp = await_command!(Bot::fetch_group_files(bot, group_id, folder_id.to_string(), start_index, count))?
+        .and_then(|rsp| rsp.list)
+        .ok_or_else(|| Error::msg(format!("Failed to list folder {} of group {}", folder_id, group_id)))?;
+    GroupError::check_ret(rsp.ret_code, rsp.ret_msg)?;
+    let mut content = GroupFolderContent::default();
+    for item in rsp.items {
+        if let Some(file) = item.file_info {
+            content.files.push(GroupFile::new(group_id, file));
+        } else if let Some(folder) = item.folder_info {
+            content.folders.push(GroupFolder::new(group_id, folder));
+        }
+    }
+    Ok((content, rsp.is_end.unwrap_or(true)))
+}
+
+/// 列出文件夹的全部内容，会自动处理分页
+pub async fn get_folder_content(bot: &Arc<Bot>, group_id: u64, folder_id: &str) -> Result<GroupFolderContent, GroupError> {
+    let mut content = GroupFolderContent::default();
+    let mut start_index = 0;
+    loop {
+        let (page, is_end) = list_folder(bot, group_id, folder_id, start_index, PAGE_SIZE).await?;
+        let page_size = (page.files.len() + page.folders.len()) as u32;
+        content.files.extend(page.files);
+        content.folders.extend(page.folders);
+        if is_end || page_size == 0 {
+            break;
+        }
+        start_index += page_size;
+    }
+    Ok(content)
+}
+
+/// 在根目录下创建文件夹，返回文件夹id
+pub async fn create_folder(bot: &Arc<Bot>, group_id: u64, folder_name: &str) -> Result<String, GroupError> {
+    let rsp = await_command!(Bot::create_group_folder(bot, group_id, folder_name.to_string()))?
+        .and_then(|rsp| rsp.create)
+        .ok_or_else(|| Error::msg("Invalid create folder response"))?;
+    GroupError::check_ret(rsp.ret_code, rsp.ret_msg)?;
+    Ok(rsp.folder_info.and_then(|f| f.folder_id).unwrap_or_default())
+}
+
+pub async fn rename_folder(bot: &Arc<Bot>, group_id: u64, folder_id: &str, new_folder_name: &str) -> Result<(), GroupError> {
+    let rsp = await_command!(Bot::rename_group_folder(bot, group_id, folder_id.to_string(), new_folder_name.to_string()))?
+        .and_then(|rsp| rsp.rename)
+        .ok_or_else(|| Error::msg("Invalid rename folder response"))?;
+    GroupError::check_ret(rsp.ret_code, rsp.ret_msg)
+}
+
+pub async fn delete_folder(bot: &Arc<Bot>, group_id: u64, folder_id: &str) -> Result<(), GroupError> {
+    let rsp = await_command!(Bot::delete_group_folder(bot, group_id, folder_id.to_string()))?
+        .and_then(|rsp| rsp.delete)
+        .ok_or_else(|| Error::msg("Invalid delete folder response"))?;
+    GroupError::check_ret(rsp.ret_code, rsp.ret_msg)
+}
+
+/// 获取群文件的下载地址
+pub async fn get_file_url(bot: &Arc<Bot>, group_id: u64, file_id: &str) -> Result<String, GroupError> {
+    let rsp = await_command!(Bot::request_group_file_download(bot, group_id, file_id.to_string()))?
+        .and_then(|rsp| rsp.download)
+        .ok_or_else(|| Error::msg("Invalid download file response"))?;
+    GroupError::check_ret(rsp.ret_code, rsp.ret_msg)?;
+    Ok(format!("https://{}/ftn_handler/{}/?fname=",
+        rsp.download_dns.unwrap_or_default(),
+        hex::encode(rsp.download_url.unwrap_or_default())
+    ))
+}
+
+pub async fn delete_file(bot: &Arc<Bot>, group_id: u64, file_id: &str) -> Result<(), GroupError> {
+    let rsp = await_command!(Bot::delete_group_file(bot, group_id, file_id.to_string()))?
+        .and_then(|rsp| rsp.delete)
+        .ok_or_else(|| Error::msg("Invalid delete file response"))?;
+    GroupError::check_ret(rsp.ret_code, rsp.ret_msg)
+}
+
+/// 将文件从`parent_folder_id`移动到`target_folder_id`
+pub async fn move_file(bot: &Arc<Bot>, group_id: u64, file_id: &str, parent_folder_id: &str, target_folder_id: &str) -> Result<(), GroupError> {
+    let rsp = await_command!(Bot::move_group_file(
+        bot, group_id, file_id.to_string(), parent_folder_id.to_string(), target_folder_id.to_string()
+    ))?
+        .and_then(|rsp| rsp.r#move)
+        .ok_or_else(|| Error::msg("Invalid move file response"))?;
+    GroupError::check_ret(rsp.ret_code, rsp.ret_msg)
+}
+
+/// 上传群文件并在群内发送，返回文件id
+pub async fn upload_file(bot: &Arc<Bot>, group_id: u64, folder_id: &str, file_name: &str, data: &[u8]) -> Result<String, GroupError> {
+    let file_md5 = md5::compute(data).0.to_vec();
+    let file_sha1 = Sha1::digest(data).to_vec();
+    let rsp = await_command!(Bot::request_group_file_upload(
+        bot, group_id, folder_id.to_string(), file_name.to_string(), data.len() as u64, file_sha1, file_md5.clone()
+    ))?
+        .and_then(|rsp| rsp.upload)
+        .ok_or_else(|| Error::msg("Invalid upload file response"))?;
+    GroupError::check_ret(rsp.ret_code, rsp.ret_msg)?;
+    let file_id = rsp.file_id.unwrap_or_default();
+
+    if !rsp.file_exist.unwrap_or(false) {
+        let self_uin = bot.client.session.read().await.uin;
+        let ext = FileUploadExt {
+            unknown1: Some(100),
+            unknown2: Some(1),
+            entry: Some(FileUploadEntry {
+                busi_buff: Some(ExcitingBusiInfo {
+                    bus_id: rsp.bus_id.or(Some(102)),
+                    sender_uin: Some(self_uin),
+                    receiver_uin: Some(group_id),
+                    group_code: Some(group_id),
+                }),
+                file_entry: Some(ExcitingFileEntry {
+                    file_size: Some(data.len() as u64),
+                    md5: Some(file_md5),
+                    check_key: rsp.check_key,
+                    md5_s2: Some(md5::compute(&data[..data.len().min(MD5_S2_SIZE)]).0.to_vec()),
+                    file_id: Some(file_id.clone()),
+                    upload_key: rsp.file_key,
+                }),
+                client_info: Some(ExcitingClientInfo {
+                    client_type: Some(3),
+                    app_id: Some("100".to_string()),
+                    terminal_type: Some(3),
+                    client_ver: Some("1.1.1".to_string()),
+                    unknown: Some(4),
+                }),
+                file_name_info: Some(ExcitingFileNameInfo {
+                    file_name: Some(file_name.to_string()),
+                }),
+                host: Some(ExcitingHostConfig {
+                    hosts: rsp.upload_ip.into_iter().map(|host| ExcitingHostInfo {
+                        url: Some(ExcitingUrlInfo {
+                            unknown: Some(1),
+                            host: Some(host),
+                        }),
+                        port: rsp.upload_port,
+                    }).collect(),
+                }),
+            }),
+            unknown200: Some(1),
+        }.encode_to_vec();
+        highway::upload(bot, 71, data, ext).await?;
+    }
+
+    let rsp = await_command!(Bot::send_group_file(bot, group_id, file_id.clone()))?
+        .and_then(|rsp| rsp.file)
+        .ok_or_else(|| Error::msg("Invalid send group file response"))?;
+    GroupError::check_ret(rsp.ret_code, rsp.ret_msg)?;
+    Ok(file_id)
+}
@@ -0,0 +1,47 @@
+use std::sync::Arc;
+use prost::Message;
+use crate::await_command;
+use crate::bot::Bot;
+use crate::pb::oidb::cmd0xeac::EssenceRsp;
+use crate::service::group::{GroupError, GroupMemberRole};
+use crate::service::group::admin::self_member;
+
+/// 设置或移除精华消息，需要管理员权限
+pub async fn set_essence(bot: &Arc<Bot>, group_id: u64, msg_seq: u32, msg_random: u32, enable: bool) -> Result<(), GroupError> {
+    if self_member(bot, group_id).await?.role == GroupMemberRole::Member {
+        return Err(GroupError::PermissionDenied("not an admin".to_string()));
+    }
+    let rsp = if enable {
+        await_command!(Bot::set_essence_message(bot, group_id, msg_seq, msg_random))?
+    } else {
+        await_command!(Bot::remove_essence_message(bot, group_id, msg_seq, msg_random))?
+    };
+    let body = GroupError::check(rsp)?;
+    // 消息不存在等错误会放在body里
+    let rsp = EssenceRsp::decode(body.as_slice()).unwrap_or_default();
+    GroupError::check_ret(rsp.error_code.map(|code| code as i32), rsp.wording)
+}
+
+/// 表情id较短的为QQ表情，emoji使用unicode码点的十进制
+fn reaction_type(code: &str) -> u32 {
+    if code.len() > 3 { 2 } else { 1 }
+}
+
+/// 给群消息贴表情或取消
+pub async fn set_reaction(bot: &Arc<Bot>, group_id: u64, msg_seq: u32, code: &str, is_add: bool) -> Result<(), GroupError> {
+    let reaction_type = reaction_type(code);
+    let rsp = if is_add {
+        await_command!(Bot::add_group_reaction(bot, group_id, msg_seq, code.to_string(), reaction_type))?
+    } else {
+        await_command!(Bot::remove_group_reaction(bot, group_id, msg_seq, code.to_string(), reaction_type))?
+    };
+    GroupError::check(rsp)?;
+    Ok(())
+}
+
+/// 在群内戳一戳成员
+pub async fn poke(bot: &Arc<Bot>, group_id: u64, user_id: u64) -> Result<(), GroupError> {
+    let rsp = await_command!(Bot::send_poke(bot, user_id, Some(group_id)))?;
+    GroupError::check(rsp)?;
+    Ok(())
+}
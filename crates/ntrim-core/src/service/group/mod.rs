@@ -0,0 +1,133 @@
+mod error;
+pub mod admin;
+pub mod settings;
+pub mod request;
+pub mod file;
+pub mod message;
+
+use std::sync::Arc;
+use anyhow::Error;
+use crate::await_command;
+use crate::bot::Bot;
+use crate::pb::oidb::cmd0xfe5::GroupEntry;
+use crate::pb::oidb::cmd0xfe7::MemberInfo;
+use crate::service::{resolver, NotFound};
+
+pub use error::GroupError;
+
+#[derive(Debug, Clone)]
+pub struct GroupInfo {
+    pub group_id: u64,
+    pub group_name: String,
+    /// 群备注
+    pub remark: String,
+    pub owner_uid: String,
+    pub member_count: u32,
+    pub max_member_count: u32,
+    pub create_time: u64,
+}
+
+impl From<GroupEntry> for GroupInfo {
+    fn from(entry: GroupEntry) -> Self {
+        let info = entry.info.unwrap_or_default();
+        Self {
+            group_id: entry.group_id,
+            group_name: info.group_name.unwrap_or_default(),
+            remark: entry.custom_info.and_then(|c| c.remark).unwrap_or_default(),
+            owner_uid: info.owner.and_then(|o| o.uid).unwrap_or_default(),
+            member_count: info.member_count.unwrap_or(0),
+            max_member_count: info.max_member_count.unwrap_or(0),
+            create_time: info.create_time.unwrap_or(0) as u64,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GroupMemberRole {
+    Owner,
+    Admin,
+    Member,
+}
+
+impl From<u32> for GroupMemberRole {
+    fn from(value: u32) -> Self {
+        match value {
+            1 => GroupMemberRole::Owner,
+            2 => GroupMemberRole::Admin,
+            _ => GroupMemberRole::Member,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GroupMemberInfo {
+    pub group_id: u64,
+    pub uid: String,
+    pub uin: u64,
+    pub nickname: String,
+    /// 群名片
+    pub card: String,
+    pub role: GroupMemberRole,
+    pub join_time: u64,
+    pub last_speak_time: u64,
+    pub level: u32,
+    /// 专属头衔
+    pub special_title: String,
+}
+
+impl GroupMemberInfo {
+    fn new(group_id: u64, info: MemberInfo) -> Self {
+        Self {
+            group_id,
+            uid: info.id.uid,
+            uin: info.id.uin,
+            nickname: info.nickname.unwrap_or_default(),
+            card: info.card.and_then(|c| c.card).unwrap_or_default(),
+            role: GroupMemberRole::from(info.role.unwrap_or(0)),
+            join_time: info.join_time.unwrap_or(0) as u64,
+            last_speak_time: info.last_speak_time.unwrap_or(0) as u64,
+            level: info.level.and_then(|l| l.level).unwrap_or(0),
+            special_title: info.special_title.unwrap_or_default(),
+        }
+    }
+}
+
+/// 拉取已加入的群列表
+pub async fn get_group_list(bot: &Arc<Bot>) -> Result<Vec<GroupInfo>, Error> {
+    match await_command!(Bot::fetch_group_list(bot))? {
+        Some(rsp) => Ok(rsp.groups.into_iter().map(GroupInfo::from).collect()),
+        None => Err(Error::msg("Failed to fetch group list")),
+    }
+}
+
+/// 拉取完整的群成员列表，会自动处理分页
+pub async fn get_group_member_list(bot: &Arc<Bot>, group_id: u64) -> Result<Vec<GroupMemberInfo>, Error> {
+    let mut members = Vec::new();
+    let mut token: Option<String> = None;
+    loop {
+        let rsp = match await_command!(Bot::fetch_group_member_list(bot, group_id, token.take()))? {
+            Some(rsp) => rsp,
+            None => return Err(Error::msg(format!("Failed to fetch member list of group {}", group_id))),
+        };
+        members.extend(rsp.members.into_iter().map(|m| {
+            resolver::learn(m.id.uin, &m.id.uid);
+            GroupMemberInfo::new(group_id, m)
+        }));
+        match rsp.token {
+            Some(next) if !next.is_empty() => token = Some(next),
+            _ => break,
+        }
+    }
+    Ok(members)
+}
+
+/// 拉取单个群成员信息
+pub async fn get_group_member_info(bot: &Arc<Bot>, group_id: u64, uid: &str) -> Result<GroupMemberInfo, Error> {
+    match await_command!(Bot::fetch_group_member_info(bot, group_id, uid.to_string()))? {
+        Some(rsp) => {
+            resolver::learn(rsp.member.id.uin, &rsp.member.id.uid);
+            Ok(GroupMemberInfo::new(rsp.group_id, rsp.member))
+        }
+        None => Err(NotFound(format!("Member {} not found in group {}", uid, group_id)).into()),
+    }
+}
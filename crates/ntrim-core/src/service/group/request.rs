@@ -0,0 +1,144 @@
+use std::sync::Arc;
+use anyhow::Error;
+use log::warn;
+use crate::await_command;
+use crate::bot::Bot;
+use crate::events::request_event::GroupRequestFlag;
+use crate::pb::oidb::cmd0x10c0::GroupRequestUser;
+use crate::service::group::GroupError;
+use crate::service::resolver;
+
+/// 一次拉取的群系统消息数量
+const FETCH_REQUEST_COUNT: u32 = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupRequestType {
+    /// 加群申请
+    Join,
+    /// 群成员邀请他人入群
+    Invite,
+    /// 邀请自己入群
+    InviteMe,
+}
+
+impl From<u32> for GroupRequestType {
+    fn from(value: u32) -> Self {
+        match value {
+            1 => GroupRequestType::Join,
+            22 => GroupRequestType::Invite,
+            _ => GroupRequestType::InviteMe,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupRequestState {
+    Pending,
+    Approved,
+    Rejected,
+}
+
+impl From<u32> for GroupRequestState {
+    fn from(value: u32) -> Self {
+        match value {
+            2 => GroupRequestState::Approved,
+            3 => GroupRequestState::Rejected,
+            _ => GroupRequestState::Pending,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct GroupRequest {
+    pub flag: GroupRequestFlag,
+    pub request_type: GroupRequestType,
+    pub state: GroupRequestState,
+    pub group_id: u64,
+    pub group_name: String,
+    /// 申请人或被邀请人
+    pub user_id: u64,
+    pub user_uid: String,
+    pub user_name: String,
+    pub invitor_id: u64,
+    pub invitor_name: String,
+    /// 处理人
+    pub operator_id: u64,
+    pub operator_name: String,
+    /// 验证消息
+    pub comment: String,
+}
+
+/// 解析请求中的用户，解析失败时记为0
+async fn resolve_user(bot: &Arc<Bot>, user: Option<GroupRequestUser>) -> (u64, String, String) {
+    let user = user.unwrap_or_default();
+    let uid = user.uid.unwrap_or_default();
+    let uin = if uid.is_empty() {
+        0
+    } else {
+        resolver::resolve_uin(bot, &uid).await.unwrap_or_else(|e| {
+            warn!("Failed to resolve uin of {}: {:?}", uid, e);
+            0
+        })
+    };
+    (uin, uid, user.name.unwrap_or_default())
+}
+
+/// 拉取群系统消息列表(加群申请与邀请)
+pub async fn get_group_requests(bot: &Arc<Bot>) -> Result<Vec<GroupRequest>, Error> {
+    let rsp = match await_command!(Bot::fetch_group_requests(bot, FETCH_REQUEST_COUNT))? {
+        Some(rsp) => rsp,
+        None => return Err(Error::msg("Failed to fetch group requests")),
+    };
+    let mut requests = Vec::with_capacity(rsp.requests.len());
+    for request in rsp.requests {
+        let (user_id, user_uid, user_name) = resolve_user(bot, request.target).await;
+        let (invitor_id, _, invitor_name) = resolve_user(bot, request.invitor).await;
+        let (operator_id, _, operator_name) = resolve_user(bot, request.operator).await;
+        requests.push(GroupRequest {
+            flag: GroupRequestFlag {
+                group_id: request.group.group_id,
+                seq: request.seq,
+                event_type: request.event_type,
+            },
+            request_type: GroupRequestType::from(request.event_type),
+            state: GroupRequestState::from(request.state),
+            group_id: request.group.group_id,
+            group_name: request.group.group_name.unwrap_or_default(),
+            user_id,
+            user_uid,
+            user_name,
+            invitor_id,
+            invitor_name,
+            operator_id,
+            operator_name,
+            comment: request.comment.unwrap_or_default(),
+        });
+    }
+    Ok(requests)
+}
+
+/// 查找符合条件的最新一条未处理请求，用于为推送补全`flag`
+pub(crate) async fn find_pending_request<F>(bot: &Arc<Bot>, predicate: F) -> Result<Option<GroupRequest>, Error>
+    where F: Fn(&GroupRequest) -> bool
+{
+    Ok(get_group_requests(bot).await?
+        .into_iter()
+        .filter(|r| r.state == GroupRequestState::Pending)
+        .filter(|r| predicate(r))
+        .max_by_key(|r| r.flag.seq))
+}
+
+/// 同意或拒绝加群申请、邀请，`block`为真时拒绝后不再接收此人的申请
+pub async fn handle_group_request(
+    bot: &Arc<Bot>,
+    flag: GroupRequestFlag,
+    approve: bool,
+    reason: &str,
+    block: bool
+) -> Result<(), GroupError> {
+    let rsp = await_command!(Bot::handle_group_request(
+        bot, flag.group_id, flag.seq, flag.event_type, approve, reason.to_string(), block && !approve
+    ))?;
+    GroupError::check(rsp)?;
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use std::sync::Arc;
+use prost::Message;
+use crate::await_command;
+use crate::bot::Bot;
+use crate::pb::trpc::highway::{GroupAvatarExtra, GroupAvatarExtraField3};
+use crate::service::{cache, highway};
+use crate::service::group::{GroupError, GroupMemberRole};
+use crate::service::group::admin::{check_manageable, self_member, target_member};
+
+/// 修改群名称
+pub async fn rename_group(bot: &Arc<Bot>, group_id: u64, group_name: &str) -> Result<(), GroupError> {
+    if self_member(bot, group_id).await?.role == GroupMemberRole::Member {
+        return Err(GroupError::PermissionDenied("not an admin".to_string()));
+    }
+    let rsp = await_command!(Bot::rename_group(bot, group_id, group_name.to_string()))?;
+    GroupError::check(rsp)?;
+    cache::on_group_rename(bot, group_id, group_name);
+    Ok(())
+}
+
+/// 修改群名片，修改他人的名片需要管理权限
+pub async fn set_member_card(bot: &Arc<Bot>, group_id: u64, user_id: u64, card: &str) -> Result<(), GroupError> {
+    let self_uin = bot.client.session.read().await.uin;
+    let target = if user_id == self_uin {
+        self_member(bot, group_id).await?
+    } else {
+        check_manageable(bot, group_id, user_id).await?
+    };
+    let rsp = await_command!(Bot::set_group_member_card(bot, group_id, target.uid, card.to_string()))?;
+    GroupError::check(rsp)?;
+    cache::on_card_change(bot, group_id, user_id, card);
+    Ok(())
+}
+
+/// 设置专属头衔，仅群主可用，`expire_time`为-1时永久有效
+pub async fn set_special_title(bot: &Arc<Bot>, group_id: u64, user_id: u64, title: &str, expire_time: i32) -> Result<(), GroupError> {
+    if self_member(bot, group_id).await?.role != GroupMemberRole::Owner {
+        return Err(GroupError::PermissionDenied("not the owner".to_string()));
+    }
+    let target = target_member(bot, group_id, user_id).await?;
+    let rsp = await_command!(Bot::set_group_special_title(bot, group_id, target.uid, title.to_string(), expire_time))?;
+    GroupError::check(rsp)?;
+    Ok(())
+}
+
+/// 上传新的群头像
+pub async fn set_avatar(bot: &Arc<Bot>, group_id: u64, image: &[u8]) -> Result<(), GroupError> {
+    if self_member(bot, group_id).await?.role == GroupMemberRole::Member {
+        return Err(GroupError::PermissionDenied("not an admin".to_string()));
+    }
+    let extra = GroupAvatarExtra {
+        r#type: Some(101),
+        group_id: Some(group_id),
+        field3: Some(GroupAvatarExtraField3 { field1: Some(1) }),
+        field5: Some(3),
+        field6: Some(1),
+    }.encode_to_vec();
+    highway::upload(bot, 3000, image, extra).await?;
+    Ok(())
+}
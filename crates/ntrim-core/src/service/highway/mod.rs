@@ -0,0 +1,185 @@
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{Duration, Instant};
+use anyhow::Error;
+use bytes::{BufMut, BytesMut};
+use log::{debug, warn};
+use prost::Message;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio::sync::Mutex;
+use tokio::time::timeout;
+use crate::await_command;
+use crate::bot::Bot;
+use crate::pb::trpc::highway::{DataHighwayHead, LoginSigHead, ReqDataHighwayHead, RespDataHighwayHead, SegHead};
+use crate::session::ticket::{SigType, TicketManager};
+
+/// 单个分片的大小
+const BLOCK_SIZE: usize = 1024 * 1024;
+
+/// 签名会话有效期，过期后重新获取
+const SESSION_TTL: Duration = Duration::from_secs(60 * 60 * 12);
+
+/// 连接与单次读写的超时时间
+const IO_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 回包头与回包体的长度上限，超过视为非法回包
+const MAX_HEAD_LEN: usize = 64 * 1024;
+const MAX_BODY_LEN: usize = 16 * 1024 * 1024;
+
+struct HighwaySession {
+    sig_session: Vec<u8>,
+    servers: Vec<SocketAddr>,
+    expire_at: Instant,
+}
+
+/// 每个Bot各自的签名会话
+#[derive(Default)]
+pub(crate) struct HighwayCache(Mutex<Option<HighwaySession>>);
+
+static SEQ: AtomicU32 = AtomicU32::new(1);
+
+/// 获取highway的签名会话与上传服务器
+async fn session(bot: &Arc<Bot>) -> Result<(Vec<u8>, Vec<SocketAddr>), Error> {
+    let mut session = bot.highway.0.lock().await;
+    if let Some(session) = session.as_ref().filter(|s| Instant::now() < s.expire_at) {
+        return Ok((session.sig_session.clone(), session.servers.clone()));
+    }
+    let rsp = match await_command!(Bot::fetch_highway_session(bot))? {
+        Some(rsp) => rsp.body,
+        None => return Err(Error::msg("Failed to fetch highway session")),
+    };
+    let servers = rsp.server_infos.into_iter()
+        .filter(|info| info.service_type == Some(1))
+        .flat_map(|info| info.server_addrs)
+        .map(|addr| SocketAddr::new(
+            Ipv4Addr::from(addr.ip.unwrap_or(0).to_le_bytes()).into(),
+            addr.port.unwrap_or(80) as u16
+        ))
+        .collect::<Vec<SocketAddr>>();
+    let sig_session = rsp.sig_session.unwrap_or_default();
+    if servers.is_empty() || sig_session.is_empty() {
+        return Err(Error::msg("Highway session is empty"));
+    }
+    debug!("Highway servers: {:?}", servers);
+    *session = Some(HighwaySession {
+        sig_session: sig_session.clone(),
+        servers: servers.clone(),
+        expire_at: Instant::now() + SESSION_TTL,
+    });
+    Ok((sig_session, servers))
+}
+
+/// 通过highway上传数据，返回服务器的扩展回包
+pub async fn upload(bot: &Arc<Bot>, command_id: u32, data: &[u8], extend: Vec<u8>) -> Result<Vec<u8>, Error> {
+    let (sig_session, servers) = session(bot).await?;
+    let mut last_error = Error::msg("No highway server available");
+    for server in servers {
+        match upload_to(bot, server, command_id, data, &sig_session, &extend).await {
+            Ok(rsp) => return Ok(rsp),
+            Err(e) => {
+                warn!("Failed to upload to highway server {}: {:?}", server, e);
+                last_error = e;
+            }
+        }
+    }
+    // 会话可能已失效，下次上传时重新获取
+    bot.highway.0.lock().await.take();
+    Err(last_error)
+}
+
+async fn upload_to(
+    bot: &Arc<Bot>,
+    server: SocketAddr,
+    command_id: u32,
+    data: &[u8],
+    sig_session: &[u8],
+    extend: &[u8]
+) -> Result<Vec<u8>, Error> {
+    let (uin, app_id, locale_id, a2) = {
+        let session = bot.client.session.read().await;
+        (
+            session.uin,
+            session.protocol.sub_app_id,
+            session.protocol.locale_id,
+            session.ticket(SigType::A2).and_then(|t| t.sig.clone()).unwrap_or_default()
+        )
+    };
+    let file_md5 = md5::compute(data).0.to_vec();
+    let mut stream = timeout(IO_TIMEOUT, TcpStream::connect(server)).await
+        .map_err(|_| Error::msg(format!("Connect to highway server {} timeout", server)))??;
+    let mut rsp_extend = Vec::new();
+    for (index, block) in data.chunks(BLOCK_SIZE).enumerate() {
+        let head = ReqDataHighwayHead {
+            base_head: DataHighwayHead {
+                version: Some(1),
+                uin: Some(uin.to_string()),
+                command: Some("PicUp.DataUp".to_string()),
+                seq: Some(SEQ.fetch_add(1, Ordering::SeqCst)),
+                retry_times: Some(0),
+                app_id: Some(app_id),
+                data_flag: Some(16),
+                command_id: Some(command_id),
+                locale_id: Some(locale_id),
+                ..Default::default()
+            },
+            seg_head: SegHead {
+                service_id: Some(0),
+                file_size: Some(data.len() as u64),
+                data_offset: Some((index * BLOCK_SIZE) as u64),
+                data_length: Some(block.len() as u32),
+                service_ticket: Some(sig_session.to_vec()),
+                md5: Some(md5::compute(block).0.to_vec()),
+                file_md5: Some(file_md5.clone()),
+                ..Default::default()
+            },
+            req_extend_info: Some(extend.to_vec()),
+            timestamp: Some(0),
+            login_sig_head: Some(LoginSigHead {
+                login_sig_type: Some(8),
+                login_sig: Some(a2.clone()),
+                app_id: Some(app_id),
+            }),
+        }.encode_to_vec();
+
+        let mut frame = BytesMut::with_capacity(head.len() + block.len() + 10);
+        frame.put_u8(0x28);
+        frame.put_u32(head.len() as u32);
+        frame.put_u32(block.len() as u32);
+        frame.put_slice(&head);
+        frame.put_slice(block);
+        frame.put_u8(0x29);
+        timeout(IO_TIMEOUT, stream.write_all(&frame)).await
+            .map_err(|_| Error::msg("Highway upload timeout"))??;
+
+        let rsp = timeout(IO_TIMEOUT, read_response(&mut stream)).await
+            .map_err(|_| Error::msg("Highway response timeout"))??;
+        if rsp.error_code.unwrap_or(0) != 0 {
+            return Err(Error::msg(format!("Highway upload failed, code: {:?}", rsp.error_code)));
+        }
+        if let Some(extend) = rsp.rsp_extend_info.filter(|e| !e.is_empty()) {
+            rsp_extend = extend;
+        }
+    }
+    Ok(rsp_extend)
+}
+
+async fn read_response(stream: &mut TcpStream) -> Result<RespDataHighwayHead, Error> {
+    if stream.read_u8().await? != 0x28 {
+        return Err(Error::msg("Invalid highway frame start"));
+    }
+    let head_len = stream.read_u32().await? as usize;
+    let body_len = stream.read_u32().await? as usize;
+    if head_len > MAX_HEAD_LEN || body_len > MAX_BODY_LEN {
+        return Err(Error::msg(format!("Highway frame too large, head: {}, body: {}", head_len, body_len)));
+    }
+    let mut head = vec![0u8; head_len];
+    stream.read_exact(&mut head).await?;
+    let mut body = vec![0u8; body_len];
+    stream.read_exact(&mut body).await?;
+    if stream.read_u8().await? != 0x29 {
+        return Err(Error::msg("Invalid highway frame end"));
+    }
+    Ok(RespDataHighwayHead::decode(head.as_slice())?)
+}
@@ -1,2 +1,25 @@
+use thiserror::Error;
+
 /// 资源上传下载相关模块
-pub mod rich_media;
\ No newline at end of file
+pub mod rich_media;
+/// 群聊相关模块
+pub mod group;
+/// 好友相关模块
+pub mod friend;
+/// uin与uid的映射解析
+pub mod resolver;
+/// 群、群成员、好友资料缓存
+pub mod cache;
+/// highway上传通道
+pub mod highway;
+/// 网页凭证(cookies、csrf token)
+pub mod credentials;
+/// qun.qq.com网页接口
+pub mod web;
+/// 资料卡与点赞
+pub mod profile;
+
+/// 查询的对象不存在，用于和网络错误、超时等区分
+#[derive(Error, Debug)]
+#[error("{0}")]
+pub struct NotFound(pub String);
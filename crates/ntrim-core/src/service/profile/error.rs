@@ -0,0 +1,36 @@
+use thiserror::Error;
+use crate::pb::oidb::TrpcOidbResponse;
+
+#[derive(Error, Debug)]
+pub enum ProfileError {
+    /// 用户不存在或资料拉取失败
+    #[error("User {0} not found")]
+    UserNotFound(u64),
+    /// 今日点赞次数已达上限
+    #[error("Like limit reached: {0}")]
+    LikeLimitReached(String),
+    /// 服务器返回的其他错误
+    #[error("Server error {code}: {msg}")]
+    Server {
+        code: u32,
+        msg: String,
+    },
+    #[error(transparent)]
+    Other(#[from] anyhow::Error),
+}
+
+impl ProfileError {
+    /// 检查点赞回包，上限错误没有独立的错误码，只能依据错误信息判断
+    pub(crate) fn check_like(rsp: Option<TrpcOidbResponse>) -> Result<(), ProfileError> {
+        let rsp = rsp.ok_or_else(|| ProfileError::Other(anyhow::Error::msg("Invalid oidb response")))?;
+        if rsp.result == 0 {
+            return Ok(());
+        }
+        let msg = rsp.error_msg.unwrap_or_default();
+        if msg.contains("上限") || msg.to_lowercase().contains("limit") {
+            Err(ProfileError::LikeLimitReached(msg))
+        } else {
+            Err(ProfileError::Server { code: rsp.result, msg })
+        }
+    }
+}
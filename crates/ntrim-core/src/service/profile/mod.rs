@@ -0,0 +1,86 @@
+mod error;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use crate::await_command;
+use crate::bot::Bot;
+use crate::service::resolver;
+
+pub use error::ProfileError;
+
+/// 资料卡字段
+const KEY_NICKNAME: u32 = 20002;
+const KEY_SEX: u32 = 20009;
+const KEY_AGE: u32 = 20037;
+const KEY_LEVEL: u32 = 105;
+const KEY_SIGN: u32 = 102;
+const KEY_QID: u32 = 27394;
+
+const PROFILE_KEYS: [u32; 6] = [KEY_NICKNAME, KEY_SEX, KEY_AGE, KEY_LEVEL, KEY_SIGN, KEY_QID];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sex {
+    Male,
+    Female,
+    Unknown,
+}
+
+impl From<u32> for Sex {
+    fn from(value: u32) -> Self {
+        match value {
+            1 => Sex::Male,
+            2 => Sex::Female,
+            _ => Sex::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct UserProfile {
+    pub uin: u64,
+    pub uid: String,
+    pub nickname: String,
+    pub sex: Sex,
+    pub age: u32,
+    pub level: u32,
+    /// 个性签名
+    pub sign: String,
+    pub qid: String,
+    pub avatar_url: String,
+}
+
+/// 拉取用户资料卡，陌生人也可以拉取
+pub async fn get_profile(bot: &Arc<Bot>, uin: u64) -> Result<UserProfile, ProfileError> {
+    let rsp = await_command!(Bot::fetch_user_info(bot, uin, PROFILE_KEYS.to_vec()))?
+        .filter(|rsp| rsp.body.uin != 0)
+        .ok_or(ProfileError::UserNotFound(uin))?;
+    let body = rsp.body;
+    resolver::learn(body.uin, &body.uid);
+    let properties = body.properties.unwrap_or_default();
+    let numbers = properties.number_properties.into_iter()
+        .map(|p| (p.key, p.value.unwrap_or(0)))
+        .collect::<HashMap<u32, u32>>();
+    let strings = properties.bytes_properties.into_iter()
+        .map(|p| (p.key, String::from_utf8_lossy(&p.value.unwrap_or_default()).to_string()))
+        .collect::<HashMap<u32, String>>();
+    let string = |key: u32| strings.get(&key).cloned().unwrap_or_default();
+    Ok(UserProfile {
+        uin: body.uin,
+        uid: body.uid,
+        nickname: string(KEY_NICKNAME),
+        sex: Sex::from(numbers.get(&KEY_SEX).cloned().unwrap_or(0)),
+        age: numbers.get(&KEY_AGE).cloned().unwrap_or(0),
+        level: numbers.get(&KEY_LEVEL).cloned().unwrap_or(0),
+        sign: string(KEY_SIGN),
+        qid: string(KEY_QID),
+        avatar_url: format!("https://q1.qlogo.cn/g?b=qq&nk={}&s=640", body.uin),
+    })
+}
+
+/// 给用户资料卡点赞`times`次，达到每日上限时返回`LikeLimitReached`
+pub async fn send_like(bot: &Arc<Bot>, uin: u64, times: u32) -> Result<(), ProfileError> {
+    let uid = resolver::resolve_uid(bot, uin).await
+        .map_err(|_| ProfileError::UserNotFound(uin))?;
+    let rsp = await_command!(Bot::send_profile_like(bot, uid, times))?;
+    ProfileError::check_like(rsp)
+}
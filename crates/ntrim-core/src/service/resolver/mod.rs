@@ -0,0 +1,152 @@
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex};
+use anyhow::Error;
+use log::{debug, warn};
+use lru::LruCache;
+use once_cell::sync::Lazy;
+use crate::await_command;
+use crate::bot::Bot;
+use crate::service::NotFound;
+
+/// 协议里一切都以uid路由，而用户只给我们uin，
+/// 这里缓存从推送、成员列表、好友列表里学到的映射关系
+struct UidCache {
+    by_uin: LruCache<u64, String>,
+    by_uid: LruCache<String, u64>,
+}
+
+static UID_CACHE: Lazy<Mutex<UidCache>> = Lazy::new(|| {
+    let size = option_env!("UID_CACHE_SIZE")
+        .map_or(4096, |v| v.parse::<usize>().unwrap_or(4096));
+    let size = NonZeroUsize::new(size).unwrap_or(NonZeroUsize::new(4096).unwrap());
+    Mutex::new(UidCache {
+        by_uin: LruCache::new(size),
+        by_uid: LruCache::new(size),
+    })
+});
+
+/// 用于查询uid时请求的资料字段(昵称)，字段越少回包越小
+const QUERY_UID_KEYS: [u32; 1] = [20002];
+
+/// 记录一组uin与uid的映射关系
+pub fn learn(uin: u64, uid: &str) {
+    if uin == 0 || uid.is_empty() {
+        return;
+    }
+    let is_new = {
+        let mut cache = UID_CACHE.lock().unwrap();
+        // uin换了uid或uid换了uin时，清理反向的旧映射，避免旧值仍能解析
+        let old_uid = cache.by_uin.put(uin, uid.to_string());
+        if let Some(old_uid) = old_uid.as_deref().filter(|old| *old != uid) {
+            if cache.by_uid.peek(old_uid) == Some(&uin) {
+                cache.by_uid.pop(old_uid);
+            }
+        }
+        if let Some(old_uin) = cache.by_uid.put(uid.to_string(), uin).filter(|old| *old != uin) {
+            if cache.by_uin.peek(&old_uin).map(|v| v.as_str()) == Some(uid) {
+                cache.by_uin.pop(&old_uin);
+            }
+        }
+        old_uid.map_or(true, |old| old != uid)
+    };
+    if is_new {
+        debug!("Learned uid mapping: {} => {}", uin, uid);
+        #[cfg(feature = "sql")]
+        persist(uin, uid.to_string());
+    }
+}
+
+#[cfg(feature = "sql")]
+fn persist(uin: u64, uid: String) {
+    use crate::db::{PG_POOL, UidRecord};
+    let pool = match PG_POOL.get() {
+        Some(pool) => pool,
+        None => return,
+    };
+    tokio::spawn(async move {
+        let record = UidRecord {
+            uin: uin as i64,
+            uid,
+            update_time: chrono::Local::now().naive_local(),
+        };
+        if let Err(e) = UidRecord::insert(pool, record).await {
+            warn!("Failed to insert uid_record to pgsql: {:?}", e);
+        }
+    });
+}
+
+fn cached_uid(uin: u64) -> Option<String> {
+    UID_CACHE.lock().unwrap().by_uin.get(&uin).cloned()
+}
+
+fn cached_uin(uid: &str) -> Option<u64> {
+    UID_CACHE.lock().unwrap().by_uid.get(uid).cloned()
+}
+
+/// 通过uin查询uid，依次查找缓存、数据库以及服务器
+pub async fn resolve_uid(bot: &Arc<Bot>, uin: u64) -> Result<String, Error> {
+    if let Some(uid) = cached_uid(uin) {
+        return Ok(uid);
+    }
+    {
+        let session = bot.client.session.read().await;
+        if session.uin == uin && !session.uid.is_empty() {
+            let uid = session.uid.clone();
+            drop(session);
+            learn(uin, &uid);
+            return Ok(uid);
+        }
+    }
+    #[cfg(feature = "sql")]
+    if let Some(pool) = crate::db::PG_POOL.get() {
+        match crate::db::UidRecord::get_by_uin(pool, uin as i64).await {
+            Ok(Some(record)) => {
+                learn(uin, &record.uid);
+                return Ok(record.uid);
+            }
+            Ok(None) => {}
+            Err(e) => warn!("Failed to query uid_record from pgsql: {:?}", e),
+        }
+    }
+    match await_command!(Bot::fetch_user_info(bot, uin, QUERY_UID_KEYS.to_vec()))? {
+        Some(rsp) if !rsp.body.uid.is_empty() => {
+            learn(rsp.body.uin, &rsp.body.uid);
+            Ok(rsp.body.uid)
+        }
+        _ => Err(NotFound(format!("Failed to resolve uid of {}", uin)).into())
+    }
+}
+
+/// 通过uid查询uin，依次查找缓存、数据库以及服务器
+pub async fn resolve_uin(bot: &Arc<Bot>, uid: &str) -> Result<u64, Error> {
+    if let Some(uin) = cached_uin(uid) {
+        return Ok(uin);
+    }
+    {
+        let session = bot.client.session.read().await;
+        if session.uid == uid {
+            let uin = session.uin;
+            drop(session);
+            learn(uin, uid);
+            return Ok(uin);
+        }
+    }
+    #[cfg(feature = "sql")]
+    if let Some(pool) = crate::db::PG_POOL.get() {
+        match crate::db::UidRecord::get_by_uid(pool, uid).await {
+            Ok(Some(record)) => {
+                learn(record.uin as u64, uid);
+                return Ok(record.uin as u64);
+            }
+            Ok(None) => {}
+            Err(e) => warn!("Failed to query uid_record from pgsql: {:?}", e),
+        }
+    }
+    match await_command!(Bot::fetch_user_info_by_uid(bot, uid.to_string(), QUERY_UID_KEYS.to_vec()))? {
+        Some(rsp) if rsp.body.uin != 0 => {
+            learn(rsp.body.uin, &rsp.body.uid);
+            Ok(rsp.body.uin)
+        }
+        _ => Err(Error::msg(format!("Failed to resolve uin of {}", uid)))
+    }
+}
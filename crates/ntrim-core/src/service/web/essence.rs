@@ -0,0 +1,93 @@
+use std::sync::Arc;
+use reqwest::Method;
+use serde_json::Value;
+use crate::bot::Bot;
+use crate::service::group::GroupError;
+use crate::service::web::{as_string, as_u64, credentials, endpoint, request, send_json};
+
+const HOST: &str = "qun.qq.com";
+
+/// 每页拉取的精华消息数量
+const PAGE_SIZE: u32 = 20;
+
+#[derive(Debug, Clone)]
+pub enum EssenceContent {
+    Text(String),
+    Face(u32),
+    Image(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct EssenceMessage {
+    pub group_id: u64,
+    pub message_seq: u64,
+    pub message_random: u64,
+    pub sender_id: u64,
+    pub sender_nick: String,
+    pub sender_time: u64,
+    /// 设精者
+    pub operator_id: u64,
+    pub operator_nick: String,
+    pub operator_time: u64,
+    pub content: Vec<EssenceContent>,
+}
+
+impl EssenceMessage {
+    fn new(group_id: u64, msg: &Value) -> Self {
+        let content = msg["msg_content"].as_array()
+            .map_or_else(Vec::new, |content| content.iter().filter_map(|item| {
+                match item["msg_type"].as_u64() {
+                    Some(1) => Some(EssenceContent::Text(as_string(&item["text"]))),
+                    Some(2) => Some(EssenceContent::Face(as_u64(&item["face_index"]) as u32)),
+                    Some(3) => Some(EssenceContent::Image(as_string(&item["image_url"]))),
+                    _ => None,
+                }
+            }).collect());
+        Self {
+            group_id,
+            message_seq: as_u64(&msg["msg_seq"]),
+            message_random: as_u64(&msg["msg_random"]),
+            sender_id: as_u64(&msg["sender_uin"]),
+            sender_nick: as_string(&msg["sender_nick"]),
+            sender_time: as_u64(&msg["sender_time"]),
+            operator_id: as_u64(&msg["add_digest_uin"]),
+            operator_nick: as_string(&msg["add_digest_nick"]),
+            operator_time: as_u64(&msg["add_digest_time"]),
+            content,
+        }
+    }
+}
+
+/// 分页获取精华消息，返回消息以及是否已到末尾
+pub async fn list_essence(bot: &Arc<Bot>, group_id: u64, page: u32) -> Result<(Vec<EssenceMessage>, bool), GroupError> {
+    let credentials = credentials(bot, HOST).await?;
+    let url = endpoint(HOST, &format!(
+        "/cgi-bin/group_digest/digest_list?bkn={}&group_code={}&page_start={}&page_limit={}",
+        credentials.csrf_token, group_id, page, PAGE_SIZE
+    ));
+    let rsp: Value = send_json(request(&credentials, Method::GET, &url)).await?;
+    GroupError::check_ret(
+        rsp["retcode"].as_i64().map(|code| code as i32),
+        rsp["retmsg"].as_str().map(|msg| msg.to_string())
+    )?;
+    let data = &rsp["data"];
+    let messages = data["msg_list"].as_array()
+        .map_or_else(Vec::new, |list| list.iter().map(|msg| EssenceMessage::new(group_id, msg)).collect());
+    Ok((messages, data["is_end"].as_bool().unwrap_or(true)))
+}
+
+/// 获取全部精华消息，会自动处理分页
+pub async fn get_essence_list(bot: &Arc<Bot>, group_id: u64) -> Result<Vec<EssenceMessage>, GroupError> {
+    let mut messages = Vec::new();
+    let mut page = 0;
+    loop {
+        let (list, is_end) = list_essence(bot, group_id, page).await?;
+        let is_empty = list.is_empty();
+        messages.extend(list);
+        if is_end || is_empty {
+            break;
+        }
+        page += 1;
+    }
+    Ok(messages)
+}
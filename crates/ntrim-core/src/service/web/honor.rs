@@ -0,0 +1,122 @@
+use std::sync::Arc;
+use anyhow::Error;
+use reqwest::Method;
+use serde_json::Value;
+use crate::bot::Bot;
+use crate::service::group::GroupError;
+use crate::service::web::{as_string, as_u64, credentials, endpoint, request, send_text};
+
+pub(super) const HOST: &str = "qun.qq.com";
+
+/// 荣誉页面会把数据写在该变量里
+const INITIAL_STATE: &str = "window.__INITIAL_STATE__=";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HonorType {
+    /// 龙王
+    Talkative,
+    /// 群聊之火
+    Performer,
+    /// 群聊炽焰
+    Legend,
+}
+
+impl HonorType {
+    fn code(&self) -> u32 {
+        match self {
+            HonorType::Talkative => 1,
+            HonorType::Performer => 2,
+            HonorType::Legend => 3,
+        }
+    }
+
+    fn list_key(&self) -> &'static str {
+        match self {
+            HonorType::Talkative => "talkativeList",
+            HonorType::Performer => "actorList",
+            HonorType::Legend => "legendList",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HonorMember {
+    pub user_id: u64,
+    pub nickname: String,
+    pub avatar: String,
+    pub description: String,
+}
+
+/// 当前龙王
+#[derive(Debug, Clone)]
+pub struct CurrentTalkative {
+    pub user_id: u64,
+    pub nickname: String,
+    pub avatar: String,
+    /// 持续天数
+    pub day_count: u32,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct GroupHonorInfo {
+    pub group_id: u64,
+    pub current_talkative: Option<CurrentTalkative>,
+    pub talkative_list: Vec<HonorMember>,
+    pub performer_list: Vec<HonorMember>,
+    pub legend_list: Vec<HonorMember>,
+}
+
+async fn fetch_honor_state(bot: &Arc<Bot>, group_id: u64, honor_type: HonorType) -> Result<Value, GroupError> {
+    let credentials = credentials(bot, HOST).await?;
+    let url = endpoint(HOST, &format!("/interactive/honorlist?gc={}&type={}", group_id, honor_type.code()));
+    let html = send_text(request(&credentials, Method::GET, &url)).await?;
+    parse_honor_state(&html, group_id)
+}
+
+/// 从荣誉页面中取出`window.__INITIAL_STATE__`
+pub(super) fn parse_honor_state(html: &str, group_id: u64) -> Result<Value, GroupError> {
+    let start = html.find(INITIAL_STATE)
+        .ok_or_else(|| Error::msg(format!("Honor state not found, group: {}", group_id)))?;
+    // 只解析第一个json值，忽略后面的脚本
+    let state = serde_json::Deserializer::from_str(&html[start + INITIAL_STATE.len()..])
+        .into_iter::<Value>()
+        .next()
+        .ok_or_else(|| Error::msg(format!("Empty honor state, group: {}", group_id)))?
+        .map_err(|e| Error::msg(format!("Invalid honor state: {}", e)))?;
+    Ok(state)
+}
+
+/// 获取群荣誉信息，只拉取`types`中的荣誉
+pub async fn get_honor_info(bot: &Arc<Bot>, group_id: u64, types: &[HonorType]) -> Result<GroupHonorInfo, GroupError> {
+    let mut info = GroupHonorInfo {
+        group_id,
+        ..Default::default()
+    };
+    for honor_type in types {
+        let state = fetch_honor_state(bot, group_id, *honor_type).await?;
+        let list = state[honor_type.list_key()].as_array()
+            .map_or_else(Vec::new, |list| list.iter().map(|member| HonorMember {
+                user_id: as_u64(&member["uin"]),
+                nickname: as_string(&member["name"]),
+                avatar: as_string(&member["avatar"]),
+                description: as_string(&member["desc"]),
+            }).collect());
+        match honor_type {
+            HonorType::Talkative => {
+                let current = &state["currentTalkative"];
+                if current.is_object() {
+                    info.current_talkative = Some(CurrentTalkative {
+                        user_id: as_u64(&current["uin"]),
+                        nickname: as_string(&current["nick"]),
+                        avatar: as_string(&current["avatar"]),
+                        day_count: as_u64(&current["day"]) as u32,
+                    });
+                }
+                info.talkative_list = list;
+            }
+            HonorType::Performer => info.performer_list = list,
+            HonorType::Legend => info.legend_list = list,
+        }
+    }
+    Ok(info)
+}
@@ -0,0 +1,142 @@
+pub mod notice;
+pub mod honor;
+pub mod essence;
+
+use std::sync::Arc;
+use std::time::Duration;
+use anyhow::Error;
+use once_cell::sync::Lazy;
+use reqwest::{Client, Method, RequestBuilder};
+use reqwest::header::{COOKIE, USER_AGENT};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use crate::bot::Bot;
+use crate::service::credentials::{self, Credentials};
+
+const WEB_USER_AGENT: &str = "Mozilla/5.0 (Linux; Android 12; Build/SKQ1.211006.001; wv) AppleWebKit/537.36 \
+    (KHTML, like Gecko) Version/4.0 Chrome/109.0.5414.86 MQQBrowser/6.2 TBS/047001 Mobile Safari/537.36 QQ/9.0.20";
+
+static CLIENT: Lazy<Client> = Lazy::new(|| {
+    Client::builder()
+        .connect_timeout(Duration::from_secs(15))
+        .timeout(Duration::from_secs(30))
+        .use_rustls_tls()
+        .build()
+        .unwrap()
+});
+
+/// 将网页接口指向其他地址的环境变量，运行时读取，便于接入本地的替身服务
+pub const QUN_WEB_API_ENV: &str = "QUN_WEB_API";
+
+/// 拼接接口地址，设置`QUN_WEB_API`后所有请求都会发往该地址
+fn endpoint(host: &str, path: &str) -> String {
+    match std::env::var(QUN_WEB_API_ENV) {
+        Ok(base) if !base.is_empty() => format!("{}{}", base.trim_end_matches('/'), path),
+        _ => format!("https://{}{}", host, path),
+    }
+}
+
+/// 获取访问`host`所需的cookies与bkn，子域名会使用上级域名的pskey
+async fn credentials(bot: &Arc<Bot>, host: &str) -> Result<Credentials, Error> {
+    credentials::get_credentials(bot, host).await
+}
+
+fn request(credentials: &Credentials, method: Method, url: &str) -> RequestBuilder {
+    CLIENT.request(method, url)
+        .header(COOKIE, credentials.cookies.as_str())
+        .header(USER_AGENT, WEB_USER_AGENT)
+}
+
+async fn send_text(builder: RequestBuilder) -> Result<String, Error> {
+    let rsp = builder.send().await?;
+    let status = rsp.status();
+    if !status.is_success() {
+        return Err(Error::msg(format!("Web api responded with status {}", status)));
+    }
+    Ok(rsp.text().await?)
+}
+
+async fn send_json<T: DeserializeOwned>(builder: RequestBuilder) -> Result<T, Error> {
+    let text = send_text(builder).await?;
+    serde_json::from_str(&text)
+        .map_err(|e| Error::msg(format!("Invalid web api response: {}, body: {}", e, text)))
+}
+
+/// 网页接口返回的文本经过了html转义
+fn unescape(text: &str) -> String {
+    text.replace("&nbsp;", " ")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&#10;", "\n")
+        .replace("&#13;", "\r")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+/// 网页接口的数字字段有时是字符串
+fn as_u64(value: &Value) -> u64 {
+    value.as_u64()
+        .or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+        .unwrap_or(0)
+}
+
+fn as_string(value: &Value) -> String {
+    value.as_str().unwrap_or_default().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use crate::service::credentials::Credentials;
+    use crate::service::group::GroupError;
+    use super::*;
+
+    /// 只应答一次的本地替身，返回请求行与固定的响应体
+    async fn stub(body: &'static str) -> (String, tokio::task::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let handle = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = vec![0u8; 4096];
+            let n = stream.read(&mut buf).await.unwrap();
+            let request = String::from_utf8_lossy(&buf[..n]).to_string();
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(), body
+            );
+            stream.write_all(response.as_bytes()).await.unwrap();
+            request
+        });
+        (base, handle)
+    }
+
+    #[tokio::test]
+    async fn web_api_is_redirected_to_local_stub() {
+        let (base, handle) = stub(
+            r#"<script>window.__INITIAL_STATE__={"talkativeList":[{"uin":"10001","name":"a&amp;b"}]};var x=1;</script>"#
+        ).await;
+        std::env::set_var(QUN_WEB_API_ENV, &base);
+        let url = endpoint(honor::HOST, "/interactive/honorlist?gc=1&type=1");
+        std::env::remove_var(QUN_WEB_API_ENV);
+        assert_eq!(url, format!("{}/interactive/honorlist?gc=1&type=1", base));
+
+        let credentials = Credentials { cookies: "uin=o10000".to_string(), csrf_token: 0 };
+        let html = send_text(request(&credentials, Method::GET, &url)).await.unwrap();
+        let state = honor::parse_honor_state(&html, 1).unwrap();
+        let member = &state["talkativeList"][0];
+        assert_eq!(as_u64(&member["uin"]), 10001);
+        assert_eq!(unescape(&as_string(&member["name"])), "a&b");
+
+        let request = handle.await.unwrap();
+        assert!(request.starts_with("GET /interactive/honorlist?gc=1&type=1 "));
+        assert!(request.contains("uin=o10000"));
+    }
+
+    #[test]
+    fn missing_honor_state_is_group_error() {
+        let err = honor::parse_honor_state("<html></html>", 1).unwrap_err();
+        assert!(matches!(err, GroupError::Other(_)));
+    }
+}
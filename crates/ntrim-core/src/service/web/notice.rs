@@ -0,0 +1,179 @@
+use std::sync::Arc;
+use anyhow::Error;
+use reqwest::Method;
+use reqwest::multipart::{Form, Part};
+use serde::Deserialize;
+use crate::bot::Bot;
+use crate::service::group::GroupError;
+use crate::service::web::{credentials, endpoint, request, send_json, unescape};
+
+const HOST: &str = "web.qun.qq.com";
+
+/// 一次拉取的公告数量
+const FETCH_NOTICE_COUNT: u32 = 20;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NoticeImage {
+    pub id: String,
+    #[serde(rename = "h", default)]
+    pub height: String,
+    #[serde(rename = "w", default)]
+    pub width: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct GroupNotice {
+    pub notice_id: String,
+    pub sender_id: u64,
+    pub publish_time: u64,
+    pub content: String,
+    pub images: Vec<NoticeImage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NoticeListRsp {
+    #[serde(default)]
+    ec: i32,
+    #[serde(default)]
+    em: String,
+    /// 置顶公告
+    #[serde(default)]
+    inst: Vec<NoticeFeed>,
+    #[serde(default)]
+    feeds: Vec<NoticeFeed>,
+}
+
+#[derive(Debug, Deserialize)]
+struct NoticeFeed {
+    #[serde(default)]
+    fid: String,
+    #[serde(default)]
+    u: u64,
+    #[serde(default)]
+    pubt: u64,
+    #[serde(default)]
+    msg: NoticeMsg,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct NoticeMsg {
+    #[serde(default)]
+    text: String,
+    #[serde(default)]
+    pics: Vec<NoticeImage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UploadImageRsp {
+    #[serde(default)]
+    ec: i32,
+    #[serde(default)]
+    em: String,
+    /// 转义后的图片信息json
+    #[serde(default)]
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SendNoticeRsp {
+    #[serde(default)]
+    ec: i32,
+    #[serde(default)]
+    em: String,
+    #[serde(default)]
+    new_fid: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommonRsp {
+    #[serde(default)]
+    ec: i32,
+    #[serde(default)]
+    em: String,
+}
+
+/// 获取群公告列表，置顶公告在前
+pub async fn get_notices(bot: &Arc<Bot>, group_id: u64) -> Result<Vec<GroupNotice>, GroupError> {
+    let credentials = credentials(bot, HOST).await?;
+    let url = endpoint(HOST, &format!(
+        "/cgi-bin/announce/get_t_list?bkn={}&qid={}&ft=23&s=-1&n={}&ni=1&i=1",
+        credentials.csrf_token, group_id, FETCH_NOTICE_COUNT
+    ));
+    let rsp: NoticeListRsp = send_json(request(&credentials, Method::GET, &url)).await?;
+    GroupError::check_ret(Some(rsp.ec), Some(rsp.em))?;
+    Ok(rsp.inst.into_iter()
+        .chain(rsp.feeds)
+        .map(|feed| GroupNotice {
+            notice_id: feed.fid,
+            sender_id: feed.u,
+            publish_time: feed.pubt,
+            content: unescape(&feed.msg.text),
+            images: feed.msg.pics,
+        })
+        .collect())
+}
+
+/// 上传公告图片
+async fn upload_image(bot: &Arc<Bot>, image: &[u8]) -> Result<NoticeImage, GroupError> {
+    let credentials = credentials(bot, HOST).await?;
+    let url = endpoint(HOST, "/cgi-bin/announce/upload_img");
+    let form = Form::new()
+        .text("bkn", credentials.csrf_token.to_string())
+        .text("source", "troopNotice")
+        .text("m", "0")
+        .part("pic_up", Part::bytes(image.to_vec()).file_name("image.png"));
+    let rsp: UploadImageRsp = send_json(request(&credentials, Method::POST, &url).multipart(form)).await?;
+    GroupError::check_ret(Some(rsp.ec), Some(rsp.em))?;
+    serde_json::from_str(&unescape(&rsp.id))
+        .map_err(|e| GroupError::Other(Error::msg(format!("Invalid notice image: {}, id: {}", e, rsp.id))))
+}
+
+/// 发布群公告，返回公告id
+pub async fn send_notice(
+    bot: &Arc<Bot>,
+    group_id: u64,
+    content: &str,
+    image: Option<&[u8]>,
+    pinned: bool,
+    confirm_required: bool
+) -> Result<String, GroupError> {
+    let image = match image {
+        Some(image) => Some(upload_image(bot, image).await?),
+        None => None,
+    };
+    let credentials = credentials(bot, HOST).await?;
+    let url = endpoint(HOST, &format!("/cgi-bin/announce/add_qun_notice?bkn={}", credentials.csrf_token));
+    let settings = serde_json::json!({
+        "is_show_edit_card": 0,
+        "tip_window_type": 1,
+        "confirm_required": confirm_required as u8,
+    }).to_string();
+    let mut form = vec![
+        ("qid", group_id.to_string()),
+        ("bkn", credentials.csrf_token.to_string()),
+        ("text", content.to_string()),
+        ("pinned", (pinned as u8).to_string()),
+        ("type", "1".to_string()),
+        ("settings", settings),
+    ];
+    if let Some(image) = image {
+        form.push(("pic", image.id));
+        form.push(("imgWidth", image.width));
+        form.push(("imgHeight", image.height));
+    }
+    let rsp: SendNoticeRsp = send_json(request(&credentials, Method::POST, &url).form(&form)).await?;
+    GroupError::check_ret(Some(rsp.ec), Some(rsp.em))?;
+    Ok(rsp.new_fid)
+}
+
+pub async fn delete_notice(bot: &Arc<Bot>, group_id: u64, notice_id: &str) -> Result<(), GroupError> {
+    let credentials = credentials(bot, HOST).await?;
+    let url = endpoint(HOST, &format!("/cgi-bin/announce/del_feed?bkn={}", credentials.csrf_token));
+    let form = [
+        ("qid", group_id.to_string()),
+        ("bkn", credentials.csrf_token.to_string()),
+        ("fid", notice_id.to_string()),
+    ];
+    let rsp: CommonRsp = send_json(request(&credentials, Method::POST, &url).form(&form)).await?;
+    GroupError::check_ret(Some(rsp.ec), Some(rsp.em))
+}
@@ -50,23 +50,28 @@ impl OlPushServlet {
     async fn on_msg_push(bot: Arc<Bot>, mut from: FromServiceMsg) -> Result<(), Error> {
         let msg = MsgPush::decode(Bytes::from(from.wup_buffer.clone()))?.msg;
         match msg.content_head.msg_type {
-            //33 => notice::on_group_member_increase(bot, msg_push),
+            33 => notice::on_group_member_increase(bot, msg).await?,
+            34 => notice::on_group_member_decrease(bot, msg).await?,
             //38 => notice::on_group_create(bot, msg_push),
+            44 => notice::on_group_admin_change(bot, msg).await?,
 
             82 => msg::on_group_msg(bot, msg),
-            //84 => notice::on_group_join_request(bot, msg_push),
+            84 => notice::on_group_join_request(bot, msg).await?,
             //85 => notice::on_group_join_request_approved(bot, msg_push),
-            //87 => notice::on_group_invite(bot, msg_push),
+            87 => notice::on_group_invitation(bot, msg).await?,
 
             //141 => msg::on_stranger_msg(bot, msg_push),
-            //166 => msg::on_friend_msg(bot, msg_push),
+            166 => msg::on_friend_msg(bot, msg),
             //167 => msg::on_unidirectional_friend_msg(bot, msg_push),
-            //187 => notice::on_friend_request_add(bot, msg_push),
+            187 => notice::on_friend_request(bot, msg).await?,
             //191 => notice::on_unidirectional_friend_increase(bot, msg_push),
             //208 => msg::on_friend_audio_msg(bot, msg_push),
 
             //525 => notice::on_group_member_invite(bot, msg_push),
-            //529 => notice::on_offline_file(bot, msg_push),
+            528 if msg.content_head.sub_type == Some(39) => notice::on_profile_change(bot, msg).await?,
+            528 if msg.content_head.sub_type == Some(290) => notice::on_friend_gray_tip(bot, msg).await?,
+            529 => notice::on_offline_file(bot, msg).await?,
+            732 if matches!(msg.content_head.sub_type, Some(16) | Some(20)) => notice::on_group_gray_tip(bot, msg).await?,
 
             _ => if option_env!("ENABLE_PRINT_UNKNOWN_PUSH").map_or(true, |v| v.parse::<bool>().unwrap()) {
                 warn!("Unknown msg type: {:?}, buf: {}", msg.content_head.msg_type, hex::encode(&from.wup_buffer))
@@ -1,6 +1,7 @@
 use std::sync::Arc;
 use log::{info, warn};
 use crate::bot::Bot;
+use crate::service::resolver;
 use crate::pb::trpc::olpush::{*};
 
 pub(super) fn on_group_msg(bot: Arc<Bot>, msg: Message) {
@@ -8,6 +9,7 @@ pub(super) fn on_group_msg(bot: Arc<Bot>, msg: Message) {
     let msg_seq = msg.content_head.msg_seq;
     let msg_uid = msg.content_head.msg_uid;
     let (sender_uid, sender_uin) = (msg.routing_head.peer_uid.unwrap(), msg.routing_head.peer_id);
+    resolver::learn(sender_uin, &sender_uid);
     let from_sub_appid = msg.routing_head.from_app_id;
     let platform = msg.routing_head.platform;
     let (group_id, sender_nick, group_name) = match msg.routing_head.contact {
@@ -34,6 +36,30 @@ pub(super) fn on_group_msg(bot: Arc<Bot>, msg: Message) {
     );
 }
 
+/// 私聊消息，文件会解码为`[CQ:file]`段并以离线文件通知上报
+pub(super) fn on_friend_msg(bot: Arc<Bot>, msg: Message) {
+    let msg_seq = msg.content_head.msg_seq;
+    let sender_uin = msg.routing_head.peer_id;
+    if let Some(uid) = msg.routing_head.peer_uid.as_ref() {
+        resolver::learn(sender_uin, uid);
+    }
+    let Some(rich_text) = msg.msg_body.rich_text else {
+        warn!("Empty rich_text, msg_seq: {}", msg_seq);
+        return;
+    };
+    let mut cq_code = decoder::parse_elements(rich_text.elems);
+    if let Some(file) = rich_text.not_online_file {
+        let file = super::notice::offline_file(file);
+        cq_code.push(file.to_cq_code());
+        super::notice::emit_offline_file(&bot, sender_uin, file);
+    }
+    if !cq_code.is_empty() {
+        info!("私聊消息 {}: {}", sender_uin,
+            cq_code.iter().map(|x| x.to_string()).collect::<Vec<String>>().join("")
+        );
+    }
+}
+
 mod decoder {
     use bytes::{Buf, Bytes};
     use log::warn;
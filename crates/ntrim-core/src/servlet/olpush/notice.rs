@@ -0,0 +1,315 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use anyhow::Error;
+use bytes::Buf;
+use log::{info, warn};
+use prost::Message as _;
+use crate::bot::Bot;
+use crate::events::BotEvent;
+use crate::events::notice_event::{MemberDecreaseType, MemberIncreaseType, NoticeEvent, OfflineFile};
+use crate::events::request_event::RequestEvent;
+use crate::service::{cache, resolver};
+use crate::service::group::request::{find_pending_request, GroupRequestType};
+use crate::pb::trpc::olpush::{*};
+
+fn msg_content(msg: Message) -> Result<Vec<u8>, Error> {
+    msg.msg_body.msg_content.ok_or_else(|| Error::msg(format!(
+        "Empty msg_content, msg_type: {}, msg_seq: {}", msg.content_head.msg_type, msg.content_head.msg_seq
+    )))
+}
+
+/// 操作者可能为空(例如主动入群)，解析失败时记为0
+async fn resolve_operator(bot: &Arc<Bot>, uid: Option<String>) -> u64 {
+    match uid.filter(|uid| !uid.is_empty()) {
+        Some(uid) => resolver::resolve_uin(bot, &uid).await.unwrap_or_else(|e| {
+            warn!("Failed to resolve operator {}: {:?}", uid, e);
+            0
+        }),
+        None => 0,
+    }
+}
+
+pub(super) async fn on_group_member_increase(bot: Arc<Bot>, msg: Message) -> Result<(), Error> {
+    let change = GroupMemberChange::decode(msg_content(msg)?.as_slice())?;
+    let member_uid = change.member_uid.unwrap_or_default();
+    let user_id = resolver::resolve_uin(&bot, &member_uid).await?;
+    let operator_uid = change.operator.map(|o| String::from_utf8_lossy(&o).to_string());
+    let operator_id = resolve_operator(&bot, operator_uid).await;
+    let sub_type = match change.increase_type {
+        Some(131) => MemberIncreaseType::Invite,
+        _ => MemberIncreaseType::Approve,
+    };
+    info!("群成员增加 [{}] {} 操作者: {}", change.group_id, user_id, operator_id);
+
+    cache::on_member_increase(&bot, change.group_id, user_id);
+    bot.emit(BotEvent::Notice(NoticeEvent::GroupMemberIncrease {
+        group_id: change.group_id,
+        user_id,
+        operator_id,
+        sub_type,
+    }));
+    Ok(())
+}
+
+pub(super) async fn on_group_member_decrease(bot: Arc<Bot>, msg: Message) -> Result<(), Error> {
+    let change = GroupMemberChange::decode(msg_content(msg)?.as_slice())?;
+    let member_uid = change.member_uid.unwrap_or_default();
+    let user_id = resolver::resolve_uin(&bot, &member_uid).await?;
+    let sub_type = match change.decrease_type {
+        Some(3) => MemberDecreaseType::KickMe,
+        Some(131) => MemberDecreaseType::Kick,
+        _ => MemberDecreaseType::Leave,
+    };
+    let operator_uid = change.operator.map(|o| match sub_type {
+        MemberDecreaseType::KickMe => GroupMemberChangeOperator::decode(o.as_slice())
+            .ok()
+            .and_then(|o| o.info)
+            .and_then(|i| i.uid)
+            .unwrap_or_default(),
+        _ => String::from_utf8_lossy(&o).to_string(),
+    });
+    let operator_id = match sub_type {
+        MemberDecreaseType::Leave => user_id,
+        _ => resolve_operator(&bot, operator_uid).await,
+    };
+    info!("群成员减少 [{}] {} 操作者: {}", change.group_id, user_id, operator_id);
+
+    if sub_type == MemberDecreaseType::KickMe || bot.client.session.read().await.uin == user_id {
+        cache::on_group_leave(&bot, change.group_id);
+    } else {
+        cache::on_member_decrease(&bot, change.group_id, user_id);
+    }
+    bot.emit(BotEvent::Notice(NoticeEvent::GroupMemberDecrease {
+        group_id: change.group_id,
+        user_id,
+        operator_id,
+        sub_type,
+    }));
+    Ok(())
+}
+
+pub(super) async fn on_group_admin_change(bot: Arc<Bot>, msg: Message) -> Result<(), Error> {
+    let change = GroupAdminChange::decode(msg_content(msg)?.as_slice())?;
+    let (extra, is_admin) = match change.body {
+        Some(GroupAdminChangeBody { enable: Some(extra), .. }) => (extra, true),
+        Some(GroupAdminChangeBody { disable: Some(extra), .. }) => (extra, false),
+        _ => return Err(Error::msg(format!("Invalid admin change body, group: {}", change.group_id))),
+    };
+    let is_admin = extra.is_promote.unwrap_or(is_admin);
+    let user_id = resolver::resolve_uin(&bot, &extra.admin_uid.unwrap_or_default()).await?;
+    info!("群管理员变动 [{}] {} 管理员: {}", change.group_id, user_id, is_admin);
+
+    cache::on_admin_change(&bot, change.group_id, user_id, is_admin);
+    bot.emit(BotEvent::Notice(NoticeEvent::GroupAdminChange {
+        group_id: change.group_id,
+        user_id,
+        is_admin,
+    }));
+    Ok(())
+}
+
+/// 资料变更，目前只处理群名片
+pub(super) async fn on_profile_change(bot: Arc<Bot>, msg: Message) -> Result<(), Error> {
+    let body = ProfileChangeBody::decode(msg_content(msg)?.as_slice())?;
+    for profile in body.infos.into_iter().filter_map(|i| i.group_member_profile) {
+        let (group_id, user_id) = match (profile.group_code.or(profile.group_id), profile.uin) {
+            (Some(group_id), Some(uin)) => (group_id, uin),
+            _ => continue,
+        };
+        for field in profile.fields {
+            if field.field != Some(1) {
+                continue;
+            }
+            let card_new = String::from_utf8_lossy(&field.value.unwrap_or_default()).to_string();
+            let card_old = cache::on_card_change(&bot, group_id, user_id, &card_new).unwrap_or_default();
+            info!("群名片变更 [{}] {}: {} -> {}", group_id, user_id, card_old, card_new);
+
+            bot.emit(BotEvent::Notice(NoticeEvent::GroupCardChange {
+                group_id,
+                user_id,
+                card_new,
+                card_old,
+            }));
+        }
+    }
+    Ok(())
+}
+
+/// 推送中没有请求的seq，需要拉取群系统消息来补全`flag`
+pub(super) async fn on_group_join_request(bot: Arc<Bot>, msg: Message) -> Result<(), Error> {
+    let join = GroupJoinRequest::decode(msg_content(msg)?.as_slice())?;
+    let target_uid = join.target_uid.unwrap_or_default();
+    let request = find_pending_request(&bot, |r| {
+        r.request_type == GroupRequestType::Join && r.group_id == join.group_id && r.user_uid == target_uid
+    }).await?;
+    let request = match request {
+        Some(request) => request,
+        None => {
+            warn!("Group join request not found in system msg, group: {}, uid: {}", join.group_id, target_uid);
+            return Ok(());
+        }
+    };
+    info!("加群申请 [{}] {}: {}", request.group_id, request.user_id, request.comment);
+
+    bot.emit(BotEvent::Request(RequestEvent::GroupJoin {
+        group_id: request.group_id,
+        user_id: request.user_id,
+        comment: request.comment,
+        flag: request.flag,
+    }));
+    Ok(())
+}
+
+pub(super) async fn on_group_invitation(bot: Arc<Bot>, msg: Message) -> Result<(), Error> {
+    let invitation = GroupInvitation::decode(msg_content(msg)?.as_slice())?;
+    let inner = invitation.info.and_then(|i| i.inner)
+        .ok_or_else(|| Error::msg("Invalid group invitation"))?;
+    let group_id = inner.group_id.unwrap_or(0);
+    let request = find_pending_request(&bot, |r| {
+        r.request_type == GroupRequestType::InviteMe && r.group_id == group_id
+    }).await?;
+    let request = match request {
+        Some(request) => request,
+        None => {
+            warn!("Group invitation not found in system msg, group: {}", group_id);
+            return Ok(());
+        }
+    };
+    info!("邀请入群 [{}] 邀请人: {}", group_id, request.invitor_id);
+
+    bot.emit(BotEvent::Request(RequestEvent::GroupInvite {
+        group_id,
+        user_id: request.invitor_id,
+        flag: request.flag,
+    }));
+    Ok(())
+}
+
+pub(super) async fn on_friend_request(bot: Arc<Bot>, msg: Message) -> Result<(), Error> {
+    let request = FriendRequest::decode(msg_content(msg)?.as_slice())?;
+    let info = request.info.ok_or_else(|| Error::msg("Invalid friend request"))?;
+    let source_uid = info.source_uid.unwrap_or_default();
+    let user_id = resolver::resolve_uin(&bot, &source_uid).await?;
+    let comment = info.message.unwrap_or_default();
+    info!("好友申请 {}: {}", user_id, comment);
+
+    bot.emit(BotEvent::Request(RequestEvent::Friend {
+        user_id,
+        comment,
+        source: info.source.unwrap_or_default(),
+        flag: source_uid,
+    }));
+    Ok(())
+}
+
+/// 私聊文件，只处理c2c_cmd为4的文件消息
+pub(super) async fn on_offline_file(bot: Arc<Bot>, msg: Message) -> Result<(), Error> {
+    if msg.content_head.c2c_cmd != Some(4) {
+        return Ok(());
+    }
+    let user_id = msg.routing_head.peer_id;
+    if let Some(uid) = msg.routing_head.peer_uid.as_ref() {
+        resolver::learn(user_id, uid);
+    }
+    let extra = FileExtra::decode(msg_content(msg)?.as_slice())?;
+    let file = extra.file.ok_or_else(|| Error::msg("Empty offline file"))?;
+    emit_offline_file(&bot, user_id, offline_file(file));
+    Ok(())
+}
+
+pub(super) fn offline_file(file: NotOnlineFile) -> OfflineFile {
+    OfflineFile {
+        file_uuid: file.file_uuid.unwrap_or_default(),
+        file_hash: file.file_hash.unwrap_or_default(),
+        file_name: file.file_name.unwrap_or_default(),
+        file_size: file.file_size.unwrap_or(0),
+        file_md5: hex::encode(file.file_md5.unwrap_or_default()),
+        expire_time: file.expire_time.unwrap_or(0) as u64,
+    }
+}
+
+/// 离线文件推送与私聊消息中的文件都以离线文件通知上报，通知中带有`[CQ:file]`段
+pub(super) fn emit_offline_file(bot: &Arc<Bot>, user_id: u64, file: OfflineFile) {
+    info!("离线文件 {}: {}({} bytes)", user_id, file.file_name, file.file_size);
+
+    bot.emit(BotEvent::Notice(NoticeEvent::OfflineFile {
+        user_id,
+        file,
+    }));
+}
+
+/// 群灰条通知，目前只处理贴表情与戳一戳
+pub(super) async fn on_group_gray_tip(bot: Arc<Bot>, msg: Message) -> Result<(), Error> {
+    let content = msg_content(msg)?;
+    let mut buf = content.as_slice();
+    if buf.remaining() < 7 {
+        return Err(Error::msg("Invalid gray tip content"));
+    }
+    buf.advance(4 + 1); // group_id(4) + 1
+    let len = buf.get_u16() as usize;
+    let body = NotifyMessageBody::decode(&buf[..len.min(buf.remaining())])?;
+    let group_id = body.group_id.unwrap_or(0);
+    if let Some(info) = body.reaction.and_then(|r| r.data).and_then(|d| d.info) {
+        on_group_reaction(bot, group_id, info).await
+    } else if let Some(tip) = body.general_gray_tip {
+        on_poke(bot, Some(group_id), tip)
+    } else {
+        Ok(())
+    }
+}
+
+/// 私聊灰条通知
+pub(super) async fn on_friend_gray_tip(bot: Arc<Bot>, msg: Message) -> Result<(), Error> {
+    let tip = GeneralGrayTipInfo::decode(msg_content(msg)?.as_slice())?;
+    on_poke(bot, None, tip)
+}
+
+async fn on_group_reaction(bot: Arc<Bot>, group_id: u64, info: GroupReactionInfo) -> Result<(), Error> {
+    let (target, detail) = match (info.target, info.detail) {
+        (Some(target), Some(detail)) => (target, detail),
+        _ => return Err(Error::msg(format!("Invalid group reaction, group: {}", group_id))),
+    };
+    let user_id = resolver::resolve_uin(&bot, &detail.operator_uid.unwrap_or_default()).await?;
+    let msg_seq = target.msg_seq.unwrap_or(0);
+    let code = detail.code.unwrap_or_default();
+    let is_add = detail.action_type != Some(2);
+    info!("群消息贴表情 [{}] {} 消息: {} 表情: {} 添加: {}", group_id, user_id, msg_seq, code, is_add);
+
+    bot.emit(BotEvent::Notice(NoticeEvent::GroupReaction {
+        group_id,
+        msg_seq,
+        user_id,
+        code,
+        count: detail.count.unwrap_or(0),
+        is_add,
+    }));
+    Ok(())
+}
+
+/// 戳一戳的灰条模板id
+const POKE_TEMPL_IDS: [u64; 5] = [1061, 1134, 1135, 1136, 10043];
+
+/// 戳一戳，模板参数中`uin_str1`为发起者，`uin_str2`为目标
+fn on_poke(bot: Arc<Bot>, group_id: Option<u64>, tip: GeneralGrayTipInfo) -> Result<(), Error> {
+    if !tip.templ_id.map_or(false, |id| POKE_TEMPL_IDS.contains(&id)) {
+        return Ok(());
+    }
+    let params = tip.msg_templ_param.into_iter()
+        .filter_map(|p| Some((p.name?, p.value.unwrap_or_default())))
+        .collect::<HashMap<String, String>>();
+    let param = |name: &str| params.get(name).cloned().unwrap_or_default();
+    let user_id = param("uin_str1").parse::<u64>()?;
+    let target_id = param("uin_str2").parse::<u64>()?;
+    let action = param("action_str");
+    let suffix = param("suffix_str");
+    info!("戳一戳 [{:?}] {} {} {} {}", group_id, user_id, action, target_id, suffix);
+
+    bot.emit(BotEvent::Notice(NoticeEvent::Poke {
+        group_id,
+        user_id,
+        target_id,
+        action,
+        suffix,
+    }));
+    Ok(())
+}
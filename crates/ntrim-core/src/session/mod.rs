@@ -1,8 +1,10 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::sync::atomic::AtomicU32;
+use tokio::sync::Notify;
 use crate::session::ticket::{SigType, Ticket, TicketManager};
 use chrono::{DateTime, Local};
+use ntrim_tools::crypto::ecdh::Ecdh;
 use log::{debug, info, warn};
 use crate::client::codec::encoder::default_tea_key;
 use crate::client::packet::packet::CommandType;
@@ -35,6 +37,8 @@ pub struct SsoSession {
     pub encrypt_a1: Vec<u8>,
     pub no_pic_sig: Vec<u8>,
     pub tgtgt_key: Vec<u8>,
+    /// 登录使用的ECDH密钥对，每个会话重新生成
+    pub ecdh: Ecdh,
 
     /// refresh sig
     pub wt_session_ticket: Vec<u8>,
@@ -46,7 +50,12 @@ pub struct SsoSession {
 
     /// Web Tickets
     pub skey: String,
-    pub pskey: String
+    pub pskey: String,
+    /// domain -> (pskey, pt4token)
+    pub pskeys: HashMap<String, (String, String)>,
+
+    /// 凭证发生变化时通知持久化服务保存会话
+    pub changed: Arc<Notify>,
 }
 
 impl SsoSession {
@@ -73,11 +82,14 @@ impl SsoSession {
             encrypt_a1: Vec::new(),
             no_pic_sig: Vec::new(),
             tgtgt_key: Vec::new(),
+            ecdh: Ecdh::default(),
             wt_session_ticket: Vec::new(),
             wt_session_key: Vec::new(),
             wt_session_create_time: 0,
             skey: String::new(),
-            pskey: String::new()
+            pskey: String::new(),
+            pskeys: HashMap::new(),
+            changed: Arc::new(Notify::new()),
         }
     }
 
@@ -100,6 +112,26 @@ impl SsoSession {
         }
     }
 
+    /// 查找域名对应的pskey与pt4token，子域名会回退到上级域名，
+    /// 例如`h5.qun.qq.com`使用`qun.qq.com`的pskey
+    pub fn web_key(&self, domain: &str) -> Option<&(String, String)> {
+        let mut domain = domain;
+        loop {
+            if let Some(key) = self.pskeys.get(domain) {
+                return Some(key);
+            }
+            match domain.split_once('.') {
+                Some((_, parent)) if parent.contains('.') => domain = parent,
+                _ => return None,
+            }
+        }
+    }
+
+    /// 通知持久化服务会话需要保存
+    pub fn mark_changed(&self) {
+        self.changed.notify_one();
+    }
+
     pub fn next_seq(&self) -> u32 {
         if self.sso_seq.load(std::sync::atomic::Ordering::SeqCst) > 800_0000 {
             self.sso_seq.store(
@@ -126,6 +158,7 @@ impl TicketManager for SsoSession {
         }
         debug!("Insert ticket: {:?}", ticket);
         self.tickets.insert(ticket.id, ticket);
+        self.mark_changed();
     }
 
     fn ticket(&self, id: SigType) -> Option<&Ticket> {
@@ -1,24 +1,268 @@
-// copy from https://github.com/lz1998/ricq/blob/46c44a3/ricq-core/src/crypto/encrypt.rs
-use tokio::sync::OnceCell;
-// use openssl::bn::{BigNum, BigNumContext};
-// use openssl::ec::{EcGroup, EcPoint, EcKey, PointConversionForm};
-// use openssl::nid::Nid;
-//use p256::{ecdh::EphemeralSecret, EncodedPoint, PublicKey};
+// inspired by https://github.com/lz1998/ricq/blob/46c44a3/ricq-core/src/crypto/encrypt.rs
+use anyhow::Error;
+use num_bigint::BigUint;
+use p256::elliptic_curve::rand_core::OsRng;
+use p256::elliptic_curve::sec1::ToEncodedPoint;
+use serde::{Deserialize, Serialize};
 
-/// 实现Ecdh？这辈子我都懒得实现！
-pub static ECDH_VERSION: i32 = 2;
+/// 服务器内置的P-256公钥，可以通过keyrotate获取最新的公钥及版本
+pub const DEFAULT_SERVER_PUBLIC_KEY: &str = "04EBCA94D733E399B2DB96EACDD3F69A8BB0F74224E2B44E3357812211D2E62EFBC91BB553098E25E33A799ADC7F76FEB208DA7C6522CDB0719A305180CC54A82E";
+pub const DEFAULT_SERVER_PUBLIC_KEY_VERSION: u16 = 1;
 
-pub async fn ecdh_public_key() -> &'static Vec<u8> {
-    static ECDH_PUB_KEY: OnceCell<Vec<u8>> = OnceCell::const_new();
-    ECDH_PUB_KEY.get_or_init(|| async {
-        hex::decode("04803E9940F3FD8E6474C3CC6994A2F972B1DA6BFDE8DDB4E775E36AB4E439DB8EA7A0E6CAFC722089F4921DFEAEFBA0F56932F3E6AA3ECF81154FD230AF32B18F").unwrap()
-    }).await
+/// 旧版协议使用的secp192k1公钥
+pub const LEGACY_SERVER_PUBLIC_KEY: &str = "04928D8850673088B343264E0C6BACB8496D697799F37211DEB25BB73906CB089FEA9639B4E0260498B51A992D50813DA8";
+pub const LEGACY_SERVER_PUBLIC_KEY_VERSION: u16 = 1;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EcdhCurve {
+    #[default]
+    Prime256v1,
+    /// 旧版协议
+    Secp192k1,
+}
+
+/// 一次登录使用的ECDH密钥对
+#[derive(Debug, Clone)]
+pub struct Ecdh {
+    pub curve: EcdhCurve,
+    /// 客户端公钥(未压缩)，随请求发送给服务器
+    pub public_key: Vec<u8>,
+    /// md5(共享密钥x坐标前16字节)，用于tea加解密
+    pub share_key: Vec<u8>,
+    pub server_public_key_version: u16,
+}
+
+impl Ecdh {
+    /// 生成新的密钥对，并与服务器公钥协商共享密钥
+    pub fn new(curve: EcdhCurve, server_public_key: &[u8], server_public_key_version: u16) -> Result<Self, Error> {
+        let (public_key, shared_x) = match curve {
+            EcdhCurve::Prime256v1 => prime256v1_exchange(&p256::SecretKey::random(&mut OsRng), server_public_key)?,
+            EcdhCurve::Secp192k1 => secp192k1_exchange(&Secp192k1::random_secret(), server_public_key)?,
+        };
+        Ok(Self::from_exchange(curve, public_key, shared_x, server_public_key_version))
+    }
+
+    /// 使用曲线内置的服务器公钥
+    pub fn with_builtin_key(curve: EcdhCurve) -> Self {
+        let (server_public_key, version) = match curve {
+            EcdhCurve::Prime256v1 => (DEFAULT_SERVER_PUBLIC_KEY, DEFAULT_SERVER_PUBLIC_KEY_VERSION),
+            EcdhCurve::Secp192k1 => (LEGACY_SERVER_PUBLIC_KEY, LEGACY_SERVER_PUBLIC_KEY_VERSION),
+        };
+        Self::new(curve, &hex::decode(server_public_key).unwrap(), version).unwrap()
+    }
+
+    fn from_exchange(curve: EcdhCurve, public_key: Vec<u8>, shared_x: Vec<u8>, server_public_key_version: u16) -> Self {
+        Self {
+            curve,
+            public_key,
+            share_key: md5::compute(&shared_x[..16]).to_vec(),
+            server_public_key_version,
+        }
+    }
+}
+
+impl Default for Ecdh {
+    fn default() -> Self {
+        Self::with_builtin_key(EcdhCurve::Prime256v1)
+    }
+}
+
+fn prime256v1_exchange(secret: &p256::SecretKey, server_public_key: &[u8]) -> Result<(Vec<u8>, Vec<u8>), Error> {
+    let server_public_key = p256::PublicKey::from_sec1_bytes(server_public_key)
+        .map_err(|e| Error::msg(format!("Invalid p256 server public key: {}", e)))?;
+    let public_key = secret.public_key().to_encoded_point(false).as_bytes().to_vec();
+    let shared = p256::ecdh::diffie_hellman(secret.to_nonzero_scalar(), server_public_key.as_affine());
+    Ok((public_key, shared.raw_secret_bytes().to_vec()))
+}
+
+/// secp192k1没有经过审计的实现，这里用大整数做仿射坐标运算，运算耗时与私钥相关，不是常数时间。
+/// 私钥每次登录重新生成且只在本地使用一次，对方无法对同一私钥反复计时，因此可以接受
+fn secp192k1_exchange(secret: &BigUint, server_public_key: &[u8]) -> Result<(Vec<u8>, Vec<u8>), Error> {
+    let curve = Secp192k1::new();
+    let server_point = curve.decode_point(server_public_key)?;
+    let public_key = curve.encode_point(&curve.mul(secret, &curve.g))?;
+    let shared = curve.mul(secret, &server_point)
+        .ok_or(Error::msg("Invalid secp192k1 shared point"))?;
+    Ok((public_key, to_fixed_bytes(&shared.0, 24)))
+}
+
+type Point = (BigUint, BigUint);
+
+struct Secp192k1 {
+    p: BigUint,
+    n: BigUint,
+    g: Option<Point>,
+}
+
+impl Secp192k1 {
+    fn new() -> Self {
+        let hex = |s: &str| BigUint::parse_bytes(s.as_bytes(), 16).unwrap();
+        Self {
+            p: hex("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFEE37"),
+            n: hex("FFFFFFFFFFFFFFFFFFFFFFFE26F2FC170F69466A74DEFD8D"),
+            g: Some((
+                hex("DB4FF10EC057E9AE26B07D0280B7F4341DA5D1B1EAE06C7D"),
+                hex("9B2F2F6D9C5628A7844163D015BE86344082AA88D95E2F9D"),
+            )),
+        }
+    }
+
+    /// [1, n)中的随机私钥
+    fn random_secret() -> BigUint {
+        let n = Self::new().n;
+        loop {
+            let bytes: [u8; 24] = rand::random();
+            let k = BigUint::from_bytes_be(&bytes) % &n;
+            if k != BigUint::from(0u8) { break k }
+        }
+    }
+
+    fn decode_point(&self, data: &[u8]) -> Result<Option<Point>, Error> {
+        if data.len() != 49 || data[0] != 0x04 {
+            return Err(Error::msg("Invalid secp192k1 server public key"));
+        }
+        let x = BigUint::from_bytes_be(&data[1..25]);
+        let y = BigUint::from_bytes_be(&data[25..]);
+        // y^2 = x^3 + 3
+        let lhs = (&y * &y) % &self.p;
+        let rhs = (&x * &x * &x + BigUint::from(3u8)) % &self.p;
+        if lhs != rhs {
+            return Err(Error::msg("Secp192k1 server public key is not on curve"));
+        }
+        Ok(Some((x, y)))
+    }
+
+    fn encode_point(&self, point: &Option<Point>) -> Result<Vec<u8>, Error> {
+        let (x, y) = point.as_ref().ok_or(Error::msg("Point at infinity"))?;
+        let mut data = vec![0x04];
+        data.extend(to_fixed_bytes(x, 24));
+        data.extend(to_fixed_bytes(y, 24));
+        Ok(data)
+    }
+
+    fn inv(&self, v: &BigUint) -> BigUint {
+        v.modpow(&(&self.p - BigUint::from(2u8)), &self.p)
+    }
+
+    fn sub(&self, a: &BigUint, b: &BigUint) -> BigUint {
+        ((a % &self.p) + &self.p - (b % &self.p)) % &self.p
+    }
+
+    fn add(&self, a: &Option<Point>, b: &Option<Point>) -> Option<Point> {
+        let (x1, y1) = match a { Some(a) => a, None => return b.clone() };
+        let (x2, y2) = match b { Some(b) => b, None => return a.clone() };
+        let lambda = if x1 == x2 {
+            if (y1 + y2) % &self.p == BigUint::from(0u8) {
+                return None;
+            }
+            // a = 0
+            (BigUint::from(3u8) * x1 * x1) % &self.p * self.inv(&(BigUint::from(2u8) * y1)) % &self.p
+        } else {
+            self.sub(y2, y1) * self.inv(&self.sub(x2, x1)) % &self.p
+        };
+        let x3 = self.sub(&self.sub(&(&lambda * &lambda), x1), x2);
+        let y3 = self.sub(&(&lambda * self.sub(x1, &x3)), y1);
+        Some((x3, y3))
+    }
+
+    fn mul(&self, k: &BigUint, point: &Option<Point>) -> Option<Point> {
+        let mut result = None;
+        for i in (0..k.bits()).rev() {
+            result = self.add(&result, &result);
+            if k.bit(i) {
+                result = self.add(&result, point);
+            }
+        }
+        result
+    }
 }
 
-pub async fn ecdh_share_key() -> &'static Vec<u8> {
-    static ECDH_SHARE_KEY: OnceCell<Vec<u8>> = OnceCell::const_new();
-    ECDH_SHARE_KEY.get_or_init(|| async {
-        hex::decode("3F539B2549AB1F71421F2C3A66298D05").unwrap()
-    }).await
+fn to_fixed_bytes(v: &BigUint, len: usize) -> Vec<u8> {
+    let bytes = v.to_bytes_be();
+    let mut data = vec![0u8; len.saturating_sub(bytes.len())];
+    data.extend(bytes);
+    data
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn p256_exchange(k: u8) -> Ecdh {
+        let mut bytes = [0u8; 32];
+        bytes[31] = k;
+        let secret = p256::SecretKey::from_slice(&bytes).unwrap();
+        let server_public_key = hex::decode(DEFAULT_SERVER_PUBLIC_KEY).unwrap();
+        let (public_key, shared_x) = prime256v1_exchange(&secret, &server_public_key).unwrap();
+        Ecdh::from_exchange(EcdhCurve::Prime256v1, public_key, shared_x, DEFAULT_SERVER_PUBLIC_KEY_VERSION)
+    }
+
+    fn secp192k1(k: u8) -> Ecdh {
+        let server_public_key = hex::decode(LEGACY_SERVER_PUBLIC_KEY).unwrap();
+        let (public_key, shared_x) = secp192k1_exchange(&BigUint::from(k), &server_public_key).unwrap();
+        Ecdh::from_exchange(EcdhCurve::Secp192k1, public_key, shared_x, LEGACY_SERVER_PUBLIC_KEY_VERSION)
+    }
+
+    #[test]
+    fn p256_public_key_from_fixed_secret() {
+        // 1G与2G，见SEC 2与NIST的P-256测试向量
+        assert_eq!(hex::encode_upper(p256_exchange(1).public_key), "04\
+            6B17D1F2E12C4247F8BCE6E563A440F277037D812DEB33A0F4A13945D898C296\
+            4FE342E2FE1A7F9B8EE7EB4A7C0F9E162BCE33576B315ECECBB6406837BF51F5");
+        assert_eq!(hex::encode_upper(p256_exchange(2).public_key), "04\
+            7CF27B188D034F7E8A52380304B51AC3C08969E277F21B35A60B48FC47669978\
+            07775510DB8ED040293D9AC69F7430DBBA7DADE63CE982299E04B79D227873D1");
+    }
+
+    #[test]
+    fn p256_share_key_is_md5_of_shared_x() {
+        // k = 1时共享点就是服务器公钥本身
+        let server_x = &hex::decode(DEFAULT_SERVER_PUBLIC_KEY).unwrap()[1..17];
+        assert_eq!(p256_exchange(1).share_key, md5::compute(server_x).to_vec());
+        // 2 * 服务器公钥的x坐标为d487e98739ba4ba9eca5cb6b21e45bda598001831f1c8a03e25b40e5fc70625e
+        assert_eq!(hex::encode(p256_exchange(2).share_key), "6cf7155833002a59cfd2c61d74576e18");
+    }
+
+    #[test]
+    fn secp192k1_public_key_from_fixed_secret() {
+        // G取自SEC 2，2G与3G分别覆盖倍点与点加
+        assert_eq!(hex::encode_upper(secp192k1(1).public_key), "04\
+            DB4FF10EC057E9AE26B07D0280B7F4341DA5D1B1EAE06C7D\
+            9B2F2F6D9C5628A7844163D015BE86344082AA88D95E2F9D");
+        assert_eq!(hex::encode_upper(secp192k1(2).public_key), "04\
+            F091CF6331B1747684F5D2549CD1D4B3A8BED93B94F93CB6\
+            FD7AF42E1E7565A02E6268661C5E42E603DA2D98A18F2ED5");
+        assert_eq!(hex::encode_upper(secp192k1(3).public_key), "04\
+            6E43B7DCAE2FD5E0BF2A1BA7615CA3B9065487C9A67B4583\
+            C48DCEA47AE08E84D5FEDC3D09E4C19606A290F7A19A6A58");
+    }
+
+    #[test]
+    fn secp192k1_share_key_is_md5_of_shared_x() {
+        let server_x = &hex::decode(LEGACY_SERVER_PUBLIC_KEY).unwrap()[1..17];
+        assert_eq!(secp192k1(1).share_key, md5::compute(server_x).to_vec());
+        // 2 * 服务器公钥的x坐标为E951EC7AA525970CC69E7BB809F6BD87E026D1CBFC63B635
+        assert_eq!(hex::encode(secp192k1(2).share_key), "4161c7926a79df0310d1f1fd508b7dda");
+    }
+
+    #[test]
+    fn secp192k1_rejects_point_off_curve() {
+        let mut key = hex::decode(LEGACY_SERVER_PUBLIC_KEY).unwrap();
+        key[48] ^= 1;
+        assert!(Ecdh::new(EcdhCurve::Secp192k1, &key, LEGACY_SERVER_PUBLIC_KEY_VERSION).is_err());
+    }
+
+    #[test]
+    fn fresh_key_pair_per_login() {
+        for curve in [EcdhCurve::Prime256v1, EcdhCurve::Secp192k1] {
+            let a = Ecdh::with_builtin_key(curve);
+            let b = Ecdh::with_builtin_key(curve);
+            assert_eq!(a.curve, curve);
+            assert_ne!(a.public_key, b.public_key);
+            assert_ne!(a.share_key, b.share_key);
+        }
+        assert_eq!(Ecdh::default().public_key.len(), 65);
+        assert_eq!(Ecdh::with_builtin_key(EcdhCurve::Secp192k1).public_key.len(), 49);
+    }
+}
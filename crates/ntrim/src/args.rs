@@ -1,4 +1,4 @@
-use clap::{Args as ClapArgs, Parser, Subcommand};
+use clap::{Args as ClapArgs, Parser, Subcommand, ValueEnum};
 
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
@@ -25,15 +25,89 @@ pub enum LoginMode {
         /// QQ密码
         #[clap(short, long)]
         password: String,
+        /// 保存session文件路径(json)
+        #[clap(short, long, default_value = "session.json")]
+        session_path: String,
+    },
+    /// 手机号验证码登录，登录成功后保存会话
+    #[clap(name = "phone")]
+    Phone {
+        /// 绑定的手机号
+        #[clap(short, long)]
+        phone: String,
+        /// 保存session文件路径(json)
+        #[clap(short, long, default_value = "session.json")]
+        session_path: String,
+    },
+    /// 扫码登录，登录成功后保存会话
+    #[clap(name = "qrlogin")]
+    QrLogin {
+        /// 保存session文件路径(json)
+        #[clap(short, long, default_value = "session.json")]
+        session_path: String,
+        /// 保存二维码图片路径
+        #[clap(short, long, default_value = "qrcode.png")]
+        qrcode_path: String,
     },
     /// 缓存会话登录(推荐)
-    #[clap(name = "session")]
+    #[clap(name = "session", subcommand_negates_reqs = true)]
     Session {
         /// session文件路径(json)
-        #[clap(short, long)]
-        session_path: String,
+        #[clap(short, long, required = true)]
+        session_path: Option<String>,
         /// 是否上线立即刷新会话
         #[clap(short, long, default_value = "false")]
         immediate_refresh: Option<bool>,
+        /// 会话文件管理，不登录
+        #[clap(subcommand)]
+        action: Option<SessionAction>,
     }
 }
+
+#[derive(Debug, Subcommand)]
+pub enum SessionAction {
+    /// 使用配置的密钥加密会话文件
+    #[clap(name = "encrypt")]
+    Encrypt {
+        /// session文件路径
+        #[clap(short, long)]
+        input: String,
+        /// 输出路径，默认覆盖原文件
+        #[clap(short, long)]
+        output: Option<String>,
+    },
+    /// 解密会话文件为明文json
+    #[clap(name = "decrypt")]
+    Decrypt {
+        /// 加密的session文件路径
+        #[clap(short, long)]
+        input: String,
+        /// 输出路径，默认覆盖原文件
+        #[clap(short, long)]
+        output: Option<String>,
+    },
+    /// 从其他框架导出的会话导入，保存为ntrim的会话文件
+    #[clap(name = "import")]
+    Import {
+        /// 来源格式
+        #[clap(short, long, value_enum)]
+        from: ImportFormat,
+        /// Shamrock导出的文件，或go-cqhttp/ricq的device.json
+        #[clap(short, long)]
+        input: String,
+        /// go-cqhttp的session.token或ricq的token.json
+        #[clap(short, long)]
+        token: Option<String>,
+        /// 保存session文件路径(json)
+        #[clap(short, long, default_value = "session.json")]
+        output: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum ImportFormat {
+    Shamrock,
+    #[value(name = "gocqhttp", alias = "go-cqhttp")]
+    GoCqhttp,
+    Ricq,
+}
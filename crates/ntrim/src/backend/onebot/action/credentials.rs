@@ -0,0 +1,36 @@
+use std::sync::Arc;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use ntrim_core::bot::Bot;
+use ntrim_core::service;
+use crate::backend::onebot::{ActionError, parse_params};
+
+#[derive(Debug, Deserialize)]
+struct GetCookiesParams {
+    #[serde(default)]
+    domain: String,
+}
+
+pub(crate) async fn get_cookies(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: GetCookiesParams = parse_params(params)?;
+    let cookies = service::credentials::get_cookies(bot, &params.domain).await?;
+    Ok(json!({
+        "cookies": cookies,
+    }))
+}
+
+pub(crate) async fn get_csrf_token(bot: &Arc<Bot>, _params: Value) -> Result<Value, ActionError> {
+    let token = service::credentials::get_csrf_token(bot).await?;
+    Ok(json!({
+        "token": token,
+    }))
+}
+
+pub(crate) async fn get_credentials(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: GetCookiesParams = parse_params(params)?;
+    let credentials = service::credentials::get_credentials(bot, &params.domain).await?;
+    Ok(json!({
+        "cookies": credentials.cookies,
+        "csrf_token": credentials.csrf_token,
+    }))
+}
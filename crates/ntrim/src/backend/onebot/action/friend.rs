@@ -0,0 +1,83 @@
+use std::sync::Arc;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use ntrim_core::bot::Bot;
+use ntrim_core::service;
+use crate::backend::onebot::{ActionError, parse_params};
+
+#[derive(Debug, Deserialize)]
+struct GetFriendListParams {
+    #[serde(default)]
+    no_cache: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct SetFriendAddRequestParams {
+    flag: String,
+    #[serde(default = "default_approve")]
+    approve: bool,
+    #[serde(default)]
+    remark: String,
+}
+
+fn default_approve() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize)]
+struct DeleteFriendParams {
+    user_id: u64,
+    #[serde(default)]
+    block: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct GetPrivateFileUrlParams {
+    file_id: String,
+    #[serde(default)]
+    file_hash: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct FriendPokeParams {
+    user_id: u64,
+}
+
+pub(crate) async fn get_friend_list(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: GetFriendListParams = parse_params(params)?;
+    let list = service::cache::friend_list(bot, params.no_cache).await?;
+    Ok(Value::Array(list.friends.iter().map(|friend| {
+        json!({
+            "user_id": friend.uin,
+            "nickname": friend.nickname,
+            "remark": friend.remark,
+            "category_id": friend.category_id,
+            "category_name": list.category(friend.category_id)
+                .map_or("", |c| c.category_name.as_str()),
+        })
+    }).collect()))
+}
+
+pub(crate) async fn set_friend_add_request(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: SetFriendAddRequestParams = parse_params(params)?;
+    service::friend::manage::handle_friend_request(bot, &params.flag, params.approve, &params.remark).await?;
+    Ok(Value::Null)
+}
+
+pub(crate) async fn delete_friend(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: DeleteFriendParams = parse_params(params)?;
+    service::friend::manage::delete_friend(bot, params.user_id, params.block).await?;
+    Ok(Value::Null)
+}
+
+pub(crate) async fn get_private_file_url(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: GetPrivateFileUrlParams = parse_params(params)?;
+    let url = service::friend::file::get_file_url(bot, &params.file_id, &params.file_hash).await?;
+    Ok(json!({ "url": url }))
+}
+
+pub(crate) async fn friend_poke(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: FriendPokeParams = parse_params(params)?;
+    service::friend::manage::poke(bot, params.user_id).await?;
+    Ok(Value::Null)
+}
@@ -0,0 +1,310 @@
+use std::sync::Arc;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use ntrim_core::bot::Bot;
+use ntrim_core::events::request_event::GroupRequestFlag;
+use ntrim_core::service::group::{GroupInfo, GroupMemberInfo, GroupMemberRole};
+use ntrim_core::service::group::request::{GroupRequest, GroupRequestState, GroupRequestType};
+use ntrim_core::service;
+use crate::backend::onebot::{ActionError, parse_params};
+
+#[derive(Debug, Deserialize)]
+struct GetGroupListParams {
+    #[serde(default)]
+    no_cache: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct GetGroupInfoParams {
+    group_id: u64,
+    #[serde(default)]
+    no_cache: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct GetGroupMemberListParams {
+    group_id: u64,
+    #[serde(default)]
+    no_cache: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct GetGroupMemberInfoParams {
+    group_id: u64,
+    user_id: u64,
+    #[serde(default)]
+    no_cache: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct SetGroupKickParams {
+    group_id: u64,
+    user_id: u64,
+    #[serde(default)]
+    reject_add_request: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct SetGroupBanParams {
+    group_id: u64,
+    user_id: u64,
+    #[serde(default = "default_ban_duration")]
+    duration: u32,
+}
+
+fn default_ban_duration() -> u32 {
+    30 * 60
+}
+
+#[derive(Debug, Deserialize)]
+struct SetGroupWholeBanParams {
+    group_id: u64,
+    #[serde(default = "default_enable")]
+    enable: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct SetGroupAdminParams {
+    group_id: u64,
+    user_id: u64,
+    #[serde(default = "default_enable")]
+    enable: bool,
+}
+
+fn default_enable() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize)]
+struct SetGroupNameParams {
+    group_id: u64,
+    group_name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SetGroupCardParams {
+    group_id: u64,
+    user_id: u64,
+    #[serde(default)]
+    card: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SetGroupSpecialTitleParams {
+    group_id: u64,
+    user_id: u64,
+    #[serde(default)]
+    special_title: String,
+    /// 有效期(秒)，-1为永久
+    #[serde(default = "default_title_duration")]
+    duration: i32,
+}
+
+fn default_title_duration() -> i32 {
+    -1
+}
+
+#[derive(Debug, Deserialize)]
+struct SetGroupAddRequestParams {
+    flag: String,
+    #[serde(default = "default_enable")]
+    approve: bool,
+    #[serde(default)]
+    reason: String,
+    /// 拒绝后不再接收此人的申请
+    #[serde(default)]
+    block: bool,
+}
+
+/// 暂无消息id映射，使用消息的seq与random定位
+#[derive(Debug, Deserialize)]
+struct EssenceMsgParams {
+    group_id: u64,
+    message_seq: u32,
+    message_random: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct SetGroupReactionParams {
+    group_id: u64,
+    message_seq: u32,
+    code: String,
+    #[serde(default = "default_enable")]
+    is_add: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct GroupPokeParams {
+    group_id: u64,
+    user_id: u64,
+}
+
+fn request_to_json(request: &GroupRequest) -> Value {
+    let checked = request.state != GroupRequestState::Pending;
+    match request.request_type {
+        GroupRequestType::Join => json!({
+            "request_id": request.flag.seq,
+            "flag": request.flag.to_string(),
+            "requester_uin": request.user_id,
+            "requester_nick": request.user_name,
+            "message": request.comment,
+            "group_id": request.group_id,
+            "group_name": request.group_name,
+            "checked": checked,
+            "actor": request.operator_id,
+        }),
+        _ => json!({
+            "request_id": request.flag.seq,
+            "flag": request.flag.to_string(),
+            "invitor_uin": request.invitor_id,
+            "invitor_nick": request.invitor_name,
+            "group_id": request.group_id,
+            "group_name": request.group_name,
+            "checked": checked,
+            "actor": request.operator_id,
+        }),
+    }
+}
+
+fn group_to_json(group: &GroupInfo) -> Value {
+    json!({
+        "group_id": group.group_id,
+        "group_name": group.group_name,
+        "group_remark": group.remark,
+        "member_count": group.member_count,
+        "max_member_count": group.max_member_count,
+        "group_create_time": group.create_time,
+    })
+}
+
+fn member_to_json(member: &GroupMemberInfo) -> Value {
+    json!({
+        "group_id": member.group_id,
+        "user_id": member.uin,
+        "nickname": member.nickname,
+        "card": member.card,
+        "sex": "unknown",
+        "age": 0,
+        "area": "",
+        "join_time": member.join_time,
+        "last_sent_time": member.last_speak_time,
+        "level": member.level.to_string(),
+        "role": match member.role {
+            GroupMemberRole::Owner => "owner",
+            GroupMemberRole::Admin => "admin",
+            GroupMemberRole::Member => "member",
+        },
+        "unfriendly": false,
+        "title": member.special_title,
+        "title_expire_time": 0,
+        "card_changeable": false,
+    })
+}
+
+pub(crate) async fn get_group_list(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: GetGroupListParams = parse_params(params)?;
+    let groups = service::cache::group_list(bot, params.no_cache).await?;
+    Ok(Value::Array(groups.iter().map(group_to_json).collect()))
+}
+
+pub(crate) async fn get_group_info(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: GetGroupInfoParams = parse_params(params)?;
+    let group = service::cache::group_info(bot, params.group_id, params.no_cache).await?;
+    Ok(group_to_json(&group))
+}
+
+pub(crate) async fn get_group_member_list(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: GetGroupMemberListParams = parse_params(params)?;
+    let members = service::cache::group_member_list(bot, params.group_id, params.no_cache).await?;
+    Ok(Value::Array(members.iter().map(member_to_json).collect()))
+}
+
+pub(crate) async fn get_group_member_info(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: GetGroupMemberInfoParams = parse_params(params)?;
+    let member = service::cache::group_member_info(bot, params.group_id, params.user_id, params.no_cache).await?;
+    Ok(member_to_json(&member))
+}
+
+pub(crate) async fn set_group_kick(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: SetGroupKickParams = parse_params(params)?;
+    service::group::admin::kick_member(bot, params.group_id, params.user_id, params.reject_add_request).await?;
+    Ok(Value::Null)
+}
+
+pub(crate) async fn set_group_ban(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: SetGroupBanParams = parse_params(params)?;
+    service::group::admin::mute_member(bot, params.group_id, params.user_id, params.duration).await?;
+    Ok(Value::Null)
+}
+
+pub(crate) async fn set_group_whole_ban(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: SetGroupWholeBanParams = parse_params(params)?;
+    service::group::admin::mute_whole_group(bot, params.group_id, params.enable).await?;
+    Ok(Value::Null)
+}
+
+pub(crate) async fn set_group_admin(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: SetGroupAdminParams = parse_params(params)?;
+    service::group::admin::set_admin(bot, params.group_id, params.user_id, params.enable).await?;
+    Ok(Value::Null)
+}
+
+pub(crate) async fn set_group_name(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: SetGroupNameParams = parse_params(params)?;
+    service::group::settings::rename_group(bot, params.group_id, &params.group_name).await?;
+    Ok(Value::Null)
+}
+
+pub(crate) async fn set_group_card(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: SetGroupCardParams = parse_params(params)?;
+    service::group::settings::set_member_card(bot, params.group_id, params.user_id, &params.card).await?;
+    Ok(Value::Null)
+}
+
+pub(crate) async fn set_group_special_title(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: SetGroupSpecialTitleParams = parse_params(params)?;
+    service::group::settings::set_special_title(bot, params.group_id, params.user_id, &params.special_title, params.duration).await?;
+    Ok(Value::Null)
+}
+
+pub(crate) async fn set_group_add_request(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: SetGroupAddRequestParams = parse_params(params)?;
+    let flag: GroupRequestFlag = params.flag.parse()
+        .map_err(|e: anyhow::Error| ActionError::BadParams(e.to_string()))?;
+    service::group::request::handle_group_request(bot, flag, params.approve, &params.reason, params.block).await?;
+    Ok(Value::Null)
+}
+
+pub(crate) async fn get_group_system_msg(bot: &Arc<Bot>, _params: Value) -> Result<Value, ActionError> {
+    let requests = service::group::request::get_group_requests(bot).await?;
+    let (join_requests, invited_requests): (Vec<&GroupRequest>, Vec<&GroupRequest>) = requests.iter()
+        .partition(|r| r.request_type == GroupRequestType::Join);
+    Ok(json!({
+        "invited_requests": invited_requests.into_iter().map(request_to_json).collect::<Vec<Value>>(),
+        "join_requests": join_requests.into_iter().map(request_to_json).collect::<Vec<Value>>(),
+    }))
+}
+
+pub(crate) async fn set_essence_msg(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: EssenceMsgParams = parse_params(params)?;
+    service::group::message::set_essence(bot, params.group_id, params.message_seq, params.message_random, true).await?;
+    Ok(Value::Null)
+}
+
+pub(crate) async fn delete_essence_msg(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: EssenceMsgParams = parse_params(params)?;
+    service::group::message::set_essence(bot, params.group_id, params.message_seq, params.message_random, false).await?;
+    Ok(Value::Null)
+}
+
+pub(crate) async fn set_group_reaction(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: SetGroupReactionParams = parse_params(params)?;
+    service::group::message::set_reaction(bot, params.group_id, params.message_seq, &params.code, params.is_add).await?;
+    Ok(Value::Null)
+}
+
+pub(crate) async fn group_poke(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: GroupPokeParams = parse_params(params)?;
+    service::group::message::poke(bot, params.group_id, params.user_id).await?;
+    Ok(Value::Null)
+}
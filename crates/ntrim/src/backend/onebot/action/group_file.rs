@@ -0,0 +1,135 @@
+use std::path::Path;
+use std::sync::Arc;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use ntrim_core::bot::Bot;
+use ntrim_core::service;
+use ntrim_core::service::group::file::{GroupFile, GroupFolder, GroupFolderContent, ROOT_FOLDER};
+use crate::backend::onebot::{ActionError, parse_params};
+
+#[derive(Debug, Deserialize)]
+struct GetGroupRootFilesParams {
+    group_id: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct GetGroupFilesByFolderParams {
+    group_id: u64,
+    folder_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GetGroupFileUrlParams {
+    group_id: u64,
+    file_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct UploadGroupFileParams {
+    group_id: u64,
+    /// 本地文件路径
+    file: String,
+    name: Option<String>,
+    folder: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CreateGroupFileFolderParams {
+    group_id: u64,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeleteGroupFolderParams {
+    group_id: u64,
+    folder_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct DeleteGroupFileParams {
+    group_id: u64,
+    file_id: String,
+}
+
+fn file_to_json(file: &GroupFile) -> Value {
+    json!({
+        "group_id": file.group_id,
+        "file_id": file.file_id,
+        "file_name": file.file_name,
+        "busid": file.bus_id,
+        "file_size": file.file_size,
+        "upload_time": file.upload_time,
+        "dead_time": file.expire_time,
+        "modify_time": file.modify_time,
+        "download_times": file.download_times,
+        "uploader": file.uploader,
+        "uploader_name": file.uploader_name,
+    })
+}
+
+fn folder_to_json(folder: &GroupFolder) -> Value {
+    json!({
+        "group_id": folder.group_id,
+        "folder_id": folder.folder_id,
+        "folder_name": folder.folder_name,
+        "create_time": folder.create_time,
+        "creator": folder.creator,
+        "creator_name": folder.creator_name,
+        "total_file_count": folder.total_file_count,
+    })
+}
+
+fn content_to_json(content: &GroupFolderContent) -> Value {
+    json!({
+        "files": content.files.iter().map(file_to_json).collect::<Vec<Value>>(),
+        "folders": content.folders.iter().map(folder_to_json).collect::<Vec<Value>>(),
+    })
+}
+
+pub(crate) async fn get_group_root_files(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: GetGroupRootFilesParams = parse_params(params)?;
+    let content = service::group::file::get_folder_content(bot, params.group_id, ROOT_FOLDER).await?;
+    Ok(content_to_json(&content))
+}
+
+pub(crate) async fn get_group_files_by_folder(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: GetGroupFilesByFolderParams = parse_params(params)?;
+    let content = service::group::file::get_folder_content(bot, params.group_id, &params.folder_id).await?;
+    Ok(content_to_json(&content))
+}
+
+pub(crate) async fn get_group_file_url(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: GetGroupFileUrlParams = parse_params(params)?;
+    let url = service::group::file::get_file_url(bot, params.group_id, &params.file_id).await?;
+    Ok(json!({ "url": url }))
+}
+
+pub(crate) async fn upload_group_file(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: UploadGroupFileParams = parse_params(params)?;
+    let path = params.file.strip_prefix("file://").unwrap_or(&params.file);
+    let data = tokio::fs::read(path).await
+        .map_err(|e| ActionError::BadParams(format!("Failed to read {}: {}", path, e)))?;
+    let name = params.name.unwrap_or_else(|| Path::new(path).file_name()
+        .map_or_else(|| "file".to_string(), |n| n.to_string_lossy().to_string()));
+    let folder = params.folder.unwrap_or_else(|| ROOT_FOLDER.to_string());
+    let file_id = service::group::file::upload_file(bot, params.group_id, &folder, &name, &data).await?;
+    Ok(json!({ "file_id": file_id }))
+}
+
+pub(crate) async fn create_group_file_folder(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: CreateGroupFileFolderParams = parse_params(params)?;
+    let folder_id = service::group::file::create_folder(bot, params.group_id, &params.name).await?;
+    Ok(json!({ "folder_id": folder_id }))
+}
+
+pub(crate) async fn delete_group_folder(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: DeleteGroupFolderParams = parse_params(params)?;
+    service::group::file::delete_folder(bot, params.group_id, &params.folder_id).await?;
+    Ok(Value::Null)
+}
+
+pub(crate) async fn delete_group_file(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: DeleteGroupFileParams = parse_params(params)?;
+    service::group::file::delete_file(bot, params.group_id, &params.file_id).await?;
+    Ok(Value::Null)
+}
@@ -0,0 +1,6 @@
+pub(super) mod group;
+pub(super) mod friend;
+pub(super) mod group_file;
+pub(super) mod credentials;
+pub(super) mod web;
+pub(super) mod profile;
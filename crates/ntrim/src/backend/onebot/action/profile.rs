@@ -0,0 +1,51 @@
+use std::sync::Arc;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use ntrim_core::bot::Bot;
+use ntrim_core::service;
+use ntrim_core::service::profile::Sex;
+use crate::backend::onebot::{ActionError, parse_params};
+
+#[derive(Debug, Deserialize)]
+struct SendLikeParams {
+    user_id: u64,
+    #[serde(default = "default_times")]
+    times: u32,
+}
+
+fn default_times() -> u32 {
+    1
+}
+
+#[derive(Debug, Deserialize)]
+struct GetStrangerInfoParams {
+    user_id: u64,
+}
+
+pub(crate) async fn send_like(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: SendLikeParams = parse_params(params)?;
+    if params.times == 0 {
+        return Err(ActionError::BadParams("times must be greater than 0".to_string()));
+    }
+    service::profile::send_like(bot, params.user_id, params.times).await?;
+    Ok(Value::Null)
+}
+
+pub(crate) async fn get_stranger_info(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: GetStrangerInfoParams = parse_params(params)?;
+    let profile = service::profile::get_profile(bot, params.user_id).await?;
+    Ok(json!({
+        "user_id": profile.uin,
+        "nickname": profile.nickname,
+        "sex": match profile.sex {
+            Sex::Male => "male",
+            Sex::Female => "female",
+            Sex::Unknown => "unknown",
+        },
+        "age": profile.age,
+        "level": profile.level,
+        "sign": profile.sign,
+        "qid": profile.qid,
+        "avatar": profile.avatar_url,
+    }))
+}
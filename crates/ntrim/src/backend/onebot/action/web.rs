@@ -0,0 +1,163 @@
+use std::sync::Arc;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use ntrim_core::bot::Bot;
+use ntrim_core::service;
+use ntrim_core::service::web::essence::EssenceContent;
+use ntrim_core::service::web::honor::{HonorMember, HonorType};
+use ntrim_tools::cqp::CQCode;
+use crate::backend::onebot::{ActionError, parse_params};
+
+#[derive(Debug, Deserialize)]
+struct SendGroupNoticeParams {
+    group_id: u64,
+    content: String,
+    /// 本地图片路径
+    #[serde(default)]
+    image: Option<String>,
+    #[serde(default)]
+    pinned: bool,
+    #[serde(default = "default_confirm_required")]
+    confirm_required: bool,
+}
+
+fn default_confirm_required() -> bool {
+    true
+}
+
+#[derive(Debug, Deserialize)]
+struct GroupIdParams {
+    group_id: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct DelGroupNoticeParams {
+    group_id: u64,
+    notice_id: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GetGroupHonorInfoParams {
+    group_id: u64,
+    #[serde(default = "default_honor_type", rename = "type")]
+    honor_type: String,
+}
+
+fn default_honor_type() -> String {
+    "all".to_string()
+}
+
+pub(crate) async fn send_group_notice(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: SendGroupNoticeParams = parse_params(params)?;
+    let image = match params.image.filter(|i| !i.is_empty()) {
+        Some(image) => {
+            let path = image.strip_prefix("file://").unwrap_or(&image);
+            Some(tokio::fs::read(path).await
+                .map_err(|e| ActionError::BadParams(format!("Failed to read {}: {}", path, e)))?)
+        }
+        None => None,
+    };
+    let notice_id = service::web::notice::send_notice(
+        bot, params.group_id, &params.content, image.as_deref(), params.pinned, params.confirm_required
+    ).await?;
+    Ok(json!({ "notice_id": notice_id }))
+}
+
+pub(crate) async fn get_group_notice(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: GroupIdParams = parse_params(params)?;
+    let notices = service::web::notice::get_notices(bot, params.group_id).await?;
+    Ok(Value::Array(notices.into_iter().map(|notice| json!({
+        "notice_id": notice.notice_id,
+        "sender_id": notice.sender_id,
+        "publish_time": notice.publish_time,
+        "message": {
+            "text": notice.content,
+            "images": notice.images.into_iter().map(|image| json!({
+                "id": image.id,
+                "height": image.height,
+                "width": image.width,
+            })).collect::<Vec<Value>>(),
+        },
+    })).collect()))
+}
+
+pub(crate) async fn del_group_notice(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: DelGroupNoticeParams = parse_params(params)?;
+    service::web::notice::delete_notice(bot, params.group_id, &params.notice_id).await?;
+    Ok(Value::Null)
+}
+
+fn honor_list_to_json(list: Vec<HonorMember>) -> Value {
+    Value::Array(list.into_iter().map(|member| json!({
+        "user_id": member.user_id,
+        "nickname": member.nickname,
+        "avatar": member.avatar,
+        "description": member.description,
+    })).collect())
+}
+
+pub(crate) async fn get_group_honor_info(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: GetGroupHonorInfoParams = parse_params(params)?;
+    let types = match params.honor_type.as_str() {
+        "talkative" => vec![HonorType::Talkative],
+        "performer" => vec![HonorType::Performer],
+        "legend" => vec![HonorType::Legend],
+        "all" => vec![HonorType::Talkative, HonorType::Performer, HonorType::Legend],
+        other => return Err(ActionError::BadParams(format!("Unsupported honor type: {}", other))),
+    };
+    let info = service::web::honor::get_honor_info(bot, params.group_id, &types).await?;
+    let mut value = json!({ "group_id": info.group_id });
+    let object = value.as_object_mut().unwrap();
+    if let Some(current) = info.current_talkative {
+        object.insert("current_talkative".to_string(), json!({
+            "user_id": current.user_id,
+            "nickname": current.nickname,
+            "avatar": current.avatar,
+            "day_count": current.day_count,
+        }));
+    }
+    for honor_type in types {
+        let (key, list) = match honor_type {
+            HonorType::Talkative => ("talkative_list", info.talkative_list.clone()),
+            HonorType::Performer => ("performer_list", info.performer_list.clone()),
+            HonorType::Legend => ("legend_list", info.legend_list.clone()),
+        };
+        object.insert(key.to_string(), honor_list_to_json(list));
+    }
+    Ok(value)
+}
+
+fn essence_to_cq_code(content: &EssenceContent) -> CQCode {
+    match content {
+        EssenceContent::Text(text) => CQCode::Text(text.clone()),
+        EssenceContent::Face(id) => CQCode::Special {
+            cq_type: "face".to_string(),
+            params: vec![("id".to_string(), id.to_string())].into_iter().collect(),
+        },
+        EssenceContent::Image(url) => CQCode::Special {
+            cq_type: "image".to_string(),
+            params: vec![
+                ("file".to_string(), url.clone()),
+                ("url".to_string(), url.clone()),
+            ].into_iter().collect(),
+        },
+    }
+}
+
+pub(crate) async fn get_essence_msg_list(bot: &Arc<Bot>, params: Value) -> Result<Value, ActionError> {
+    let params: GroupIdParams = parse_params(params)?;
+    let messages = service::web::essence::get_essence_list(bot, params.group_id).await?;
+    Ok(Value::Array(messages.into_iter().map(|msg| json!({
+        "sender_id": msg.sender_id,
+        "sender_nick": msg.sender_nick,
+        "sender_time": msg.sender_time,
+        "operator_id": msg.operator_id,
+        "operator_nick": msg.operator_nick,
+        "operator_time": msg.operator_time,
+        "message_seq": msg.message_seq,
+        "message_random": msg.message_random,
+        "content": msg.content.iter()
+            .map(|c| essence_to_cq_code(c).to_string())
+            .collect::<String>(),
+    })).collect()))
+}
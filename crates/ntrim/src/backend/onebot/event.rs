@@ -0,0 +1,139 @@
+use chrono::Local;
+use serde_json::{json, Value};
+use ntrim_core::events::BotEvent;
+use ntrim_core::events::notice_event::{MemberDecreaseType, MemberIncreaseType, NoticeEvent, OfflineFile};
+use ntrim_core::events::request_event::RequestEvent;
+
+/// 将事件转换为OneBot 11的上报格式，不支持的事件返回`None`
+pub fn event_to_json(self_id: u64, event: &BotEvent) -> Option<Value> {
+    let mut value = match event {
+        BotEvent::Notice(notice) => notice_to_json(notice),
+        BotEvent::Request(request) => request_to_json(request),
+    }?;
+    let object = value.as_object_mut()?;
+    object.insert("time".to_string(), json!(Local::now().timestamp()));
+    object.insert("self_id".to_string(), json!(self_id));
+    Some(value)
+}
+
+fn notice_to_json(notice: &NoticeEvent) -> Option<Value> {
+    Some(match notice {
+        NoticeEvent::GroupMemberIncrease { group_id, user_id, operator_id, sub_type } => json!({
+            "post_type": "notice",
+            "notice_type": "group_increase",
+            "sub_type": match sub_type {
+                MemberIncreaseType::Approve => "approve",
+                MemberIncreaseType::Invite => "invite",
+            },
+            "group_id": group_id,
+            "user_id": user_id,
+            "operator_id": operator_id,
+        }),
+        NoticeEvent::GroupMemberDecrease { group_id, user_id, operator_id, sub_type } => json!({
+            "post_type": "notice",
+            "notice_type": "group_decrease",
+            "sub_type": match sub_type {
+                MemberDecreaseType::Leave => "leave",
+                MemberDecreaseType::Kick => "kick",
+                MemberDecreaseType::KickMe => "kick_me",
+            },
+            "group_id": group_id,
+            "user_id": user_id,
+            "operator_id": operator_id,
+        }),
+        NoticeEvent::GroupAdminChange { group_id, user_id, is_admin } => json!({
+            "post_type": "notice",
+            "notice_type": "group_admin",
+            "sub_type": if *is_admin { "set" } else { "unset" },
+            "group_id": group_id,
+            "user_id": user_id,
+        }),
+        NoticeEvent::GroupCardChange { group_id, user_id, card_new, card_old } => json!({
+            "post_type": "notice",
+            "notice_type": "group_card",
+            "group_id": group_id,
+            "user_id": user_id,
+            "card_new": card_new,
+            "card_old": card_old,
+        }),
+        NoticeEvent::GroupReaction { group_id, msg_seq, user_id, code, count, is_add } => json!({
+            "post_type": "notice",
+            "notice_type": "reaction",
+            "sub_type": if *is_add { "add" } else { "remove" },
+            "group_id": group_id,
+            "message_seq": msg_seq,
+            "operator_id": user_id,
+            "code": code,
+            "count": count,
+        }),
+        NoticeEvent::Poke { group_id: Some(group_id), user_id, target_id, action, suffix } => json!({
+            "post_type": "notice",
+            "notice_type": "notify",
+            "sub_type": "poke",
+            "group_id": group_id,
+            "user_id": user_id,
+            "target_id": target_id,
+            "action": action,
+            "suffix": suffix,
+        }),
+        NoticeEvent::Poke { group_id: None, user_id, target_id, action, suffix } => json!({
+            "post_type": "notice",
+            "notice_type": "notify",
+            "sub_type": "poke",
+            "sender_id": user_id,
+            "user_id": user_id,
+            "target_id": target_id,
+            "action": action,
+            "suffix": suffix,
+        }),
+        // `file_id`与`file_hash`用于`get_private_file_url`，`message`为对应的`[CQ:file]`段
+        NoticeEvent::OfflineFile { user_id, file } => json!({
+            "post_type": "notice",
+            "notice_type": "offline_file",
+            "user_id": user_id,
+            "file": offline_file_to_json(file),
+            "message": file.to_cq_code().to_string(),
+        }),
+    })
+}
+
+fn offline_file_to_json(file: &OfflineFile) -> Value {
+    json!({
+        "file_id": file.file_uuid,
+        "file_hash": file.file_hash,
+        "name": file.file_name,
+        "size": file.file_size,
+        "md5": file.file_md5,
+        "expire": file.expire_time,
+    })
+}
+
+fn request_to_json(request: &RequestEvent) -> Option<Value> {
+    Some(match request {
+        RequestEvent::GroupJoin { group_id, user_id, comment, flag } => json!({
+            "post_type": "request",
+            "request_type": "group",
+            "sub_type": "add",
+            "group_id": group_id,
+            "user_id": user_id,
+            "comment": comment,
+            "flag": flag.to_string(),
+        }),
+        RequestEvent::GroupInvite { group_id, user_id, flag } => json!({
+            "post_type": "request",
+            "request_type": "group",
+            "sub_type": "invite",
+            "group_id": group_id,
+            "user_id": user_id,
+            "comment": "",
+            "flag": flag.to_string(),
+        }),
+        RequestEvent::Friend { user_id, comment, flag, .. } => json!({
+            "post_type": "request",
+            "request_type": "friend",
+            "user_id": user_id,
+            "comment": comment,
+            "flag": flag,
+        }),
+    })
+}
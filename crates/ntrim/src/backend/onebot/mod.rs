@@ -0,0 +1,147 @@
+mod action;
+mod event;
+mod ws;
+
+use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use thiserror::Error;
+use ntrim_core::bot::Bot;
+use ntrim_core::service::group::GroupError;
+use ntrim_core::service::profile::ProfileError;
+use crate::config::OneBotConfig;
+
+pub use event::event_to_json;
+
+/// 启动OneBot 11后端，动作与事件通过正向WebSocket传输
+pub fn start(bot: Arc<Bot>, config: &OneBotConfig) {
+    let config = config.clone();
+    tokio::spawn(async move {
+        if let Err(e) = ws::serve(bot, config).await {
+            error!("OneBot websocket server stopped: {}", e);
+        }
+    });
+}
+
+/// OneBot 11 动作请求
+#[derive(Debug, Clone, Deserialize)]
+pub struct ActionRequest {
+    pub action: String,
+    #[serde(default)]
+    pub params: Value,
+    #[serde(default)]
+    pub echo: Option<Value>,
+}
+
+/// OneBot 11 动作响应
+#[derive(Debug, Clone, Serialize)]
+pub struct ActionResponse {
+    pub status: &'static str,
+    pub retcode: i32,
+    pub data: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub msg: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub echo: Option<Value>,
+}
+
+#[derive(Error, Debug)]
+pub enum ActionError {
+    #[error("Invalid params: {0}")]
+    BadParams(String),
+    #[error("Unsupported action: {0}")]
+    Unsupported(String),
+    #[error("{0}")]
+    Group(#[from] GroupError),
+    #[error("{0}")]
+    Profile(#[from] ProfileError),
+    #[error("{0}")]
+    Failed(#[from] anyhow::Error),
+}
+
+impl ActionError {
+    fn retcode(&self) -> i32 {
+        match self {
+            ActionError::BadParams(_) => 1400,
+            ActionError::Unsupported(_) => 1404,
+            ActionError::Group(GroupError::Other(_)) => 100,
+            ActionError::Group(_) => 103,
+            ActionError::Profile(ProfileError::Other(_)) => 100,
+            ActionError::Profile(_) => 103,
+            ActionError::Failed(_) => 100,
+        }
+    }
+}
+
+pub(crate) fn parse_params<T: DeserializeOwned>(params: Value) -> Result<T, ActionError> {
+    let params = if params.is_null() { Value::Object(Default::default()) } else { params };
+    serde_json::from_value(params).map_err(|e| ActionError::BadParams(e.to_string()))
+}
+
+/// 处理一个OneBot动作请求
+pub async fn handle_action(bot: &Arc<Bot>, request: ActionRequest) -> ActionResponse {
+    let ActionRequest { action, params, echo } = request;
+    debug!("OneBot action: {}, params: {}", action, params);
+    let result = match action.as_str() {
+        "get_group_list" => action::group::get_group_list(bot, params).await,
+        "get_group_info" => action::group::get_group_info(bot, params).await,
+        "get_group_member_list" => action::group::get_group_member_list(bot, params).await,
+        "get_group_member_info" => action::group::get_group_member_info(bot, params).await,
+        "set_group_kick" => action::group::set_group_kick(bot, params).await,
+        "set_group_ban" => action::group::set_group_ban(bot, params).await,
+        "set_group_whole_ban" => action::group::set_group_whole_ban(bot, params).await,
+        "set_group_admin" => action::group::set_group_admin(bot, params).await,
+        "set_group_name" => action::group::set_group_name(bot, params).await,
+        "set_group_card" => action::group::set_group_card(bot, params).await,
+        "set_group_special_title" => action::group::set_group_special_title(bot, params).await,
+        "set_group_add_request" => action::group::set_group_add_request(bot, params).await,
+        "get_group_system_msg" => action::group::get_group_system_msg(bot, params).await,
+        "set_essence_msg" => action::group::set_essence_msg(bot, params).await,
+        "delete_essence_msg" => action::group::delete_essence_msg(bot, params).await,
+        "set_group_reaction" => action::group::set_group_reaction(bot, params).await,
+        "group_poke" => action::group::group_poke(bot, params).await,
+        "get_group_root_files" => action::group_file::get_group_root_files(bot, params).await,
+        "get_group_files_by_folder" => action::group_file::get_group_files_by_folder(bot, params).await,
+        "get_group_file_url" => action::group_file::get_group_file_url(bot, params).await,
+        "upload_group_file" => action::group_file::upload_group_file(bot, params).await,
+        "create_group_file_folder" => action::group_file::create_group_file_folder(bot, params).await,
+        "delete_group_folder" => action::group_file::delete_group_folder(bot, params).await,
+        "delete_group_file" => action::group_file::delete_group_file(bot, params).await,
+        "get_friend_list" => action::friend::get_friend_list(bot, params).await,
+        "set_friend_add_request" => action::friend::set_friend_add_request(bot, params).await,
+        "delete_friend" => action::friend::delete_friend(bot, params).await,
+        "get_private_file_url" => action::friend::get_private_file_url(bot, params).await,
+        "friend_poke" => action::friend::friend_poke(bot, params).await,
+        "send_like" => action::profile::send_like(bot, params).await,
+        "get_stranger_info" => action::profile::get_stranger_info(bot, params).await,
+        "get_cookies" => action::credentials::get_cookies(bot, params).await,
+        "get_csrf_token" => action::credentials::get_csrf_token(bot, params).await,
+        "get_credentials" => action::credentials::get_credentials(bot, params).await,
+        "_send_group_notice" => action::web::send_group_notice(bot, params).await,
+        "_get_group_notice" => action::web::get_group_notice(bot, params).await,
+        "_del_group_notice" => action::web::del_group_notice(bot, params).await,
+        "get_group_honor_info" => action::web::get_group_honor_info(bot, params).await,
+        "get_essence_msg_list" => action::web::get_essence_msg_list(bot, params).await,
+        _ => Err(ActionError::Unsupported(action.clone()))
+    };
+    match result {
+        Ok(data) => ActionResponse {
+            status: "ok",
+            retcode: 0,
+            data,
+            msg: None,
+            echo,
+        },
+        Err(e) => {
+            warn!("OneBot action {} failed: {}", action, e);
+            ActionResponse {
+                status: "failed",
+                retcode: e.retcode(),
+                data: Value::Null,
+                msg: Some(e.to_string()),
+                echo,
+            }
+        }
+    }
+}
@@ -0,0 +1,122 @@
+use std::sync::Arc;
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{json, Value};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tokio_tungstenite::tungstenite::http::StatusCode;
+use tokio_tungstenite::tungstenite::Message;
+use chrono::Local;
+use ntrim_core::bot::Bot;
+use crate::backend::onebot::{event_to_json, handle_action, ActionRequest, ActionResponse};
+use crate::config::OneBotConfig;
+
+/// OneBot 11 正向WebSocket，同一连接上处理动作请求并推送事件
+pub async fn serve(bot: Arc<Bot>, config: OneBotConfig) -> std::io::Result<()> {
+    let listener = TcpListener::bind(&config.ws_address).await?;
+    info!("OneBot websocket listening on ws://{}", config.ws_address);
+    let access_token = config.access_token.filter(|token| !token.is_empty());
+    loop {
+        let (stream, addr) = listener.accept().await?;
+        let bot = bot.clone();
+        let access_token = access_token.clone();
+        tokio::spawn(async move {
+            debug!("OneBot websocket connection from {}", addr);
+            if let Err(e) = handle_connection(bot, stream, access_token).await {
+                warn!("OneBot websocket connection {} closed: {}", addr, e);
+            }
+        });
+    }
+}
+
+async fn handle_connection(bot: Arc<Bot>, stream: TcpStream, access_token: Option<String>) -> anyhow::Result<()> {
+    let ws = tokio_tungstenite::accept_hdr_async(stream, |request: &Request, response: Response| {
+        match &access_token {
+            Some(token) if !is_authorized(request, token) => {
+                let mut error = ErrorResponse::new(Some("Unauthorized".to_string()));
+                *error.status_mut() = StatusCode::UNAUTHORIZED;
+                Err(error)
+            }
+            _ => Ok(response),
+        }
+    }).await?;
+    let (mut sink, mut stream) = ws.split();
+    let self_id = bot.client.session.read().await.uin;
+
+    let (tx, mut rx) = mpsc::channel::<Value>(64);
+    let writer = tokio::spawn(async move {
+        while let Some(value) = rx.recv().await {
+            if sink.send(Message::Text(value.to_string())).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let _ = tx.send(json!({
+        "time": Local::now().timestamp(),
+        "self_id": self_id,
+        "post_type": "meta_event",
+        "meta_event_type": "lifecycle",
+        "sub_type": "connect",
+    })).await;
+
+    let mut events = bot.subscribe();
+    let event_tx = tx.clone();
+    let forwarder = tokio::spawn(async move {
+        loop {
+            match events.recv().await {
+                Ok(event) => if let Some(value) = event_to_json(self_id, &event) {
+                    if event_tx.send(value).await.is_err() { break }
+                }
+                Err(RecvError::Lagged(n)) => warn!("OneBot websocket lagged, {} events dropped", n),
+                Err(RecvError::Closed) => break,
+            }
+        }
+    });
+
+    while let Some(message) = stream.next().await {
+        let text = match message? {
+            Message::Text(text) => text,
+            Message::Close(_) => break,
+            _ => continue,
+        };
+        let request: ActionRequest = match serde_json::from_str(&text) {
+            Ok(request) => request,
+            Err(e) => {
+                let response = ActionResponse {
+                    status: "failed",
+                    retcode: 1400,
+                    data: Value::Null,
+                    msg: Some(format!("Invalid action request: {}", e)),
+                    echo: None,
+                };
+                let _ = tx.send(serde_json::to_value(&response).unwrap_or_default()).await;
+                continue;
+            }
+        };
+        let bot = bot.clone();
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            let response = handle_action(&bot, request).await;
+            let _ = tx.send(serde_json::to_value(&response).unwrap_or_default()).await;
+        });
+    }
+    forwarder.abort();
+    writer.abort();
+    Ok(())
+}
+
+/// 支持`Authorization: Bearer <token>`请求头与`access_token`查询参数
+fn is_authorized(request: &Request, token: &str) -> bool {
+    let header = request.headers().get("Authorization")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.strip_prefix("Bearer "))
+        .map(|v| v.trim());
+    if header == Some(token) {
+        return true;
+    }
+    request.uri().query().map_or(false, |query| {
+        query.split('&').any(|pair| pair.split_once('=') == Some(("access_token", token)))
+    })
+}
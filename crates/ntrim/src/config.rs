@@ -3,12 +3,54 @@ use std::future::Future;
 use std::path::PathBuf;
 use serde_derive::{Deserialize, Serialize};
 use toml::Value;
+use ntrim_tools::crypto::ecdh::EcdhCurve;
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Config {
     pub qsign: QSign,
     pub developer: Developer,
     pub sql: Sql,
+    #[serde(default)]
+    pub session: SessionConfig,
+    #[serde(default)]
+    pub onebot: OneBotConfig,
+    #[serde(default)]
+    pub ecdh: EcdhConfig,
+}
+
+/// 登录时的ECDH密钥协商
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct EcdhConfig {
+    /// `prime256v1`或旧版协议使用的`secp192k1`
+    #[serde(default)]
+    pub curve: EcdhCurve,
+    /// 从keyrotate获取服务器最新的P-256公钥，下发的公钥没有签名校验，获取失败时使用内置公钥
+    #[serde(default)]
+    pub rotate_key: bool,
+}
+
+/// OneBot 11 正向WebSocket
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct OneBotConfig {
+    /// 监听地址
+    #[serde(default = "default_onebot_ws_address")]
+    pub ws_address: String,
+    /// 为空时不校验
+    #[serde(default)]
+    pub access_token: Option<String>,
+}
+
+impl Default for OneBotConfig {
+    fn default() -> Self {
+        Self {
+            ws_address: default_onebot_ws_address(),
+            access_token: None,
+        }
+    }
+}
+
+fn default_onebot_ws_address() -> String {
+    "127.0.0.1:5800".to_string()
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -26,6 +68,33 @@ pub struct Sql {
     pub address: String,
 }
 
+/// 会话文件的加密与保存，口令通过环境变量NTRIM_SESSION_PASSPHRASE传入
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SessionConfig {
+    /// 为true时必须配置密钥，禁止明文保存会话
+    #[serde(default)]
+    pub encrypt: bool,
+    /// 密钥文件，优先于口令
+    pub key_file: Option<String>,
+    /// 定时保存会话的间隔(秒)
+    #[serde(default = "default_save_interval")]
+    pub save_interval: u64,
+}
+
+impl Default for SessionConfig {
+    fn default() -> Self {
+        Self {
+            encrypt: false,
+            key_file: None,
+            save_interval: default_save_interval(),
+        }
+    }
+}
+
+fn default_save_interval() -> u64 {
+    5 * 60
+}
+
 pub fn parse_local_config(path: PathBuf) -> Option<Config> {
     info!("Local config file: {}", path.to_str().unwrap());
     if let Ok(contents) = read_to_string(path) {
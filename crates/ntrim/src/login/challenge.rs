@@ -0,0 +1,26 @@
+use tokio::io::{AsyncBufReadExt, BufReader};
+use ntrim_core::commands::wtlogin::challenge::LoginChallengeHandler;
+
+/// 命令行登录验证，从标准输入读取ticket与短信验证码
+pub struct CliChallengeHandler;
+
+impl LoginChallengeHandler for CliChallengeHandler {
+    async fn on_captcha(&self, url: &str) -> Option<String> {
+        info!("Captcha required, please open the url and finish the slider verification: {}", url);
+        read_line("Please input the ticket: ").await
+    }
+
+    async fn on_sms(&self, phone: &str) -> Option<String> {
+        info!("Sms code has been sent to {}", phone);
+        read_line("Please input the sms code: ").await
+    }
+}
+
+/// 读取一行输入，空行或输入结束视为放弃
+async fn read_line(prompt: &str) -> Option<String> {
+    info!("{}", prompt);
+    let mut line = String::new();
+    BufReader::new(tokio::io::stdin()).read_line(&mut line).await.ok()?;
+    let line = line.trim().to_string();
+    if line.is_empty() { None } else { Some(line) }
+}
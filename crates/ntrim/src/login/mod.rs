@@ -1,2 +1,35 @@
+use std::sync::Arc;
+use tokio::sync::mpsc::Receiver;
+use ntrim_core::bot::Bot;
+use ntrim_core::events::wtlogin_event::WtloginResponse;
+use crate::config::Config;
+use crate::login::session::register::{new_session, save_session};
+use crate::login::session::token_login;
+use crate::qqsecurity::QSecurityViaHTTP;
 
-pub mod session;
\ No newline at end of file
+pub mod session;
+pub mod qrlogin;
+pub mod password;
+pub mod phone;
+mod challenge;
+
+/// 创建一个全新会话的Bot，用于扫码、密码等登录方式
+async fn new_bot(config: &Config) -> Arc<Bot> {
+    let bot = Bot::new(
+        new_session(), Arc::new(QSecurityViaHTTP::new(&config.qsign.server))
+    ).await.map_err(|e| {
+        error!("Failed to create bot session instance: {}", e)
+    }).unwrap();
+    bot.client.renew_ecdh(config.ecdh.curve, config.ecdh.rotate_key).await;
+    bot
+}
+
+/// 登录成功后保存会话，断开登录用的连接并以缓存会话的方式上线
+async fn online_with_new_session(bot: Arc<Bot>, session_path: String, config: &Config) -> (Arc<Bot>, Receiver<WtloginResponse>) {
+    let session = bot.client.session.read().await;
+    save_session(&session_path, &session);
+    drop(session);
+    bot.client.disconnect().await;
+
+    token_login(session_path, config).await
+}
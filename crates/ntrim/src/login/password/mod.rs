@@ -0,0 +1,49 @@
+use std::process::exit;
+use std::sync::Arc;
+use tokio::sync::mpsc::Receiver;
+use ntrim_core::bot::Bot;
+use ntrim_core::commands::wtlogin::password_login::PasswordLogin;
+use ntrim_core::commands::wtlogin::challenge::resolve_login_challenge;
+use ntrim_core::commands::wtlogin::wtlogin_request::{WtloginFactory, WtloginBuilder};
+use ntrim_core::events::wtlogin_event::WtloginResponse;
+use ntrim_core::refresh_session::refresh_pskey_domains;
+use crate::config::Config;
+use crate::login::{new_bot, online_with_new_session};
+use crate::login::challenge::CliChallengeHandler;
+
+/// 账号密码登录，登录成功后保存会话并以缓存会话的方式上线
+pub async fn password_login(qq: String, password: String, session_path: String, config: &Config) -> (Arc<Bot>, Receiver<WtloginResponse>) {
+    let uin: u64 = qq.parse().unwrap_or_else(|_| {
+        error!("Invalid qq number: {}", qq);
+        exit(1);
+    });
+    let bot = new_bot(config).await;
+
+    let mut session = bot.client.session.write().await;
+    session.uin = uin;
+    session.tgtgt_key = rand::random::<[u8; 16]>().to_vec();
+    drop(session);
+
+    info!("Logging in {} with password", uin);
+    let password_md5 = md5::compute(password.as_bytes()).0;
+    let rx = WtloginBuilder::<PasswordLogin>::build(bot.client.clone(), (
+        password_md5, refresh_pskey_domains().clone()
+    )).send().await;
+    let resp = rx.await.unwrap_or_else(|e| {
+        error!("Password login failed: {}", e);
+        exit(1);
+    });
+    match resolve_login_challenge(&bot.client, resp, &CliChallengeHandler).await {
+        WtloginResponse::Success() => {}
+        WtloginResponse::Fail(e) => {
+            error!("Password login failed: {}", e);
+            exit(1);
+        }
+        resp => {
+            error!("Password login failed, unexpected response: {:?}", resp);
+            exit(1);
+        }
+    }
+
+    online_with_new_session(bot, session_path, config).await
+}
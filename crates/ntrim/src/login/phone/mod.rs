@@ -0,0 +1,64 @@
+use std::process::exit;
+use std::sync::Arc;
+use tokio::sync::mpsc::Receiver;
+use ntrim_core::bot::Bot;
+use ntrim_core::commands::wtlogin::challenge::{resolve_login_challenge, LoginChallengeHandler};
+use ntrim_core::commands::wtlogin::request_phone_sms::RequestPhoneSms;
+use ntrim_core::commands::wtlogin::submit_phone_sms::SubmitPhoneSms;
+use ntrim_core::commands::wtlogin::wtlogin_request::{WtloginFactory, WtloginBuilder};
+use ntrim_core::events::wtlogin_event::WtloginResponse;
+use crate::config::Config;
+use crate::login::{new_bot, online_with_new_session};
+use crate::login::challenge::CliChallengeHandler;
+
+/// 手机号验证码登录，登录成功后保存会话并以缓存会话的方式上线
+pub async fn phone_login(phone: String, session_path: String, config: &Config) -> (Arc<Bot>, Receiver<WtloginResponse>) {
+    let bot = new_bot(config).await;
+
+    let mut session = bot.client.session.write().await;
+    session.tgtgt_key = rand::random::<[u8; 16]>().to_vec();
+    drop(session);
+
+    info!("Requesting sms code for {}", phone);
+    let rx = WtloginBuilder::<RequestPhoneSms>::build(bot.client.clone(), phone.clone())
+        .send().await;
+    let ctx = match rx.await {
+        Ok(WtloginResponse::SmsCodeSent { ctx }) => ctx,
+        Ok(WtloginResponse::Fail(e)) => {
+            error!("Failed to request sms code: {}", e);
+            exit(1);
+        }
+        Ok(resp) => {
+            error!("Failed to request sms code, unexpected response: {:?}", resp);
+            exit(1);
+        }
+        Err(e) => {
+            error!("Failed to request sms code: {}", e);
+            exit(1);
+        }
+    };
+    let Some(code) = CliChallengeHandler.on_sms(&phone).await else {
+        error!("Phone login canceled");
+        exit(1);
+    };
+
+    let rx = WtloginBuilder::<SubmitPhoneSms>::build(bot.client.clone(), (phone, code, ctx))
+        .send().await;
+    let resp = rx.await.unwrap_or_else(|e| {
+        error!("Phone login failed: {}", e);
+        exit(1);
+    });
+    match resolve_login_challenge(&bot.client, resp, &CliChallengeHandler).await {
+        WtloginResponse::Success() => {}
+        WtloginResponse::Fail(e) => {
+            error!("Phone login failed: {}", e);
+            exit(1);
+        }
+        resp => {
+            error!("Phone login failed, unexpected response: {:?}", resp);
+            exit(1);
+        }
+    }
+
+    online_with_new_session(bot, session_path, config).await
+}
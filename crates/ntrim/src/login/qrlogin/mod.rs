@@ -0,0 +1,105 @@
+use std::process::exit;
+use std::sync::Arc;
+use tokio::sync::mpsc::Receiver;
+use ntrim_core::bot::Bot;
+use ntrim_core::commands::wtlogin::qrcode_login::QrCodeLogin;
+use ntrim_core::commands::wtlogin::trans_emp::{fetch_qrcode, query_qrcode_state, QrCode, QrCodeConfirmed, QrCodeState};
+use ntrim_core::commands::wtlogin::challenge::resolve_login_challenge;
+use ntrim_core::commands::wtlogin::wtlogin_request::{WtloginFactory, WtloginBuilder};
+use ntrim_core::events::wtlogin_event::WtloginResponse;
+use ntrim_core::refresh_session::refresh_pskey_domains;
+use crate::config::Config;
+use crate::login::{new_bot, online_with_new_session};
+use crate::login::challenge::CliChallengeHandler;
+
+/// 扫码登录，登录成功后保存会话并以缓存会话的方式上线
+pub async fn qrcode_login(session_path: String, qrcode_path: String, config: &Config) -> (Arc<Bot>, Receiver<WtloginResponse>) {
+    let bot = new_bot(config).await;
+
+    let confirmed = loop {
+        let qrcode = fetch_qrcode(&bot.client).await.unwrap_or_else(|e| {
+            error!("Failed to fetch login qrcode: {}", e);
+            exit(1);
+        });
+        show_qrcode(&qrcode, &qrcode_path);
+        if let Some(confirmed) = wait_for_confirm(&bot, &qrcode).await {
+            break confirmed;
+        }
+        info!("Qrcode expired, fetching a new one");
+    };
+    info!("Qrcode confirmed by {}, logging in", confirmed.uin);
+    let _ = std::fs::remove_file(&qrcode_path);
+
+    let mut session = bot.client.session.write().await;
+    session.uin = confirmed.uin;
+    session.tgtgt_key = confirmed.tgtgt_key;
+    drop(session);
+
+    let rx = WtloginBuilder::<QrCodeLogin>::build(bot.client.clone(), (
+        confirmed.tmp_pwd,
+        confirmed.tmp_no_pic_sig,
+        confirmed.tgt_qr,
+        refresh_pskey_domains().clone()
+    )).send().await;
+    let resp = rx.await.unwrap_or_else(|e| {
+        error!("Qrcode login failed: {}", e);
+        exit(1);
+    });
+    match resolve_login_challenge(&bot.client, resp, &CliChallengeHandler).await {
+        WtloginResponse::Success() => {}
+        WtloginResponse::Fail(e) => {
+            error!("Qrcode login failed: {}", e);
+            exit(1);
+        }
+        resp => {
+            error!("Qrcode login failed, unexpected response: {:?}", resp);
+            exit(1);
+        }
+    }
+
+    online_with_new_session(bot, session_path, config).await
+}
+
+/// 轮询扫码状态，二维码过期时返回None
+async fn wait_for_confirm(bot: &Arc<Bot>, qrcode: &QrCode) -> Option<QrCodeConfirmed> {
+    let mut scanned = false;
+    loop {
+        tokio::time::sleep(std::time::Duration::from_secs(3)).await;
+        match query_qrcode_state(&bot.client, &qrcode.sig).await {
+            Ok(QrCodeState::WaitingForScan) => {}
+            Ok(QrCodeState::WaitingForConfirm) => if !scanned {
+                scanned = true;
+                info!("Qrcode scanned, please confirm on your phone");
+            }
+            Ok(QrCodeState::Confirmed(confirmed)) => return Some(confirmed),
+            Ok(QrCodeState::Expired) => return None,
+            Ok(QrCodeState::Canceled) => {
+                error!("Qrcode login canceled on the phone");
+                exit(1);
+            }
+            Err(e) => warn!("Failed to query qrcode state: {}", e),
+        }
+    }
+}
+
+fn show_qrcode(qrcode: &QrCode, qrcode_path: &str) {
+    if let Err(e) = std::fs::write(qrcode_path, &qrcode.image) {
+        warn!("Failed to save qrcode to {}: {}", qrcode_path, e);
+    } else {
+        info!("Qrcode saved to {}", qrcode_path);
+    }
+    let Some(url) = &qrcode.url else {
+        info!("Please scan the qrcode image with mobile QQ");
+        return;
+    };
+    match qrcode::QrCode::new(url.as_bytes()) {
+        Ok(code) => {
+            let image = code.render::<qrcode::render::unicode::Dense1x2>()
+                .dark_color(qrcode::render::unicode::Dense1x2::Light)
+                .light_color(qrcode::render::unicode::Dense1x2::Dark)
+                .build();
+            info!("Please scan the qrcode with mobile QQ:\n{}", image);
+        }
+        Err(e) => warn!("Failed to render qrcode in terminal: {}", e),
+    }
+}
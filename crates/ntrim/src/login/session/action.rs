@@ -0,0 +1,36 @@
+use crate::args::{ImportFormat, SessionAction};
+use crate::login::session::crypto::session_cipher;
+use crate::login::session::file::{SessionFile, SessionFileError};
+use crate::login::session::import::{import_gocqhttp, import_ricq, import_shamrock};
+
+/// 执行会话文件管理命令
+pub fn run_session_action(action: SessionAction) -> Result<(), SessionFileError> {
+    match action {
+        SessionAction::Encrypt { input, output } => {
+            let cipher = session_cipher().ok_or(SessionFileError::Crypto(
+                "no session key configured, please set session.key_file or NTRIM_SESSION_PASSPHRASE".to_string()
+            ))?;
+            let output = output.unwrap_or_else(|| input.clone());
+            SessionFile::read_with(&input, Some(cipher))?.write_with(&output, Some(cipher))?;
+            info!("Encrypted session {} to {}", input, output);
+        }
+        SessionAction::Decrypt { input, output } => {
+            let output = output.unwrap_or_else(|| input.clone());
+            SessionFile::read_with(&input, session_cipher())?.write_with(&output, None)?;
+            warn!("Decrypted session {} to {}, credentials are stored in plaintext now", input, output);
+        }
+        SessionAction::Import { from, input, token, output } => {
+            let token_path = || token.clone().ok_or(SessionFileError::invalid(
+                "--token", format!("token file is required when importing from {:?}", from)
+            ));
+            let session = match from {
+                ImportFormat::Shamrock => import_shamrock(&input)?,
+                ImportFormat::GoCqhttp => import_gocqhttp(&input, &token_path()?)?,
+                ImportFormat::Ricq => import_ricq(&input, &token_path()?)?,
+            };
+            SessionFile::from_session(&session).write(&output)?;
+            info!("Imported session of {} from {:?} to {}", session.uin, from, output);
+        }
+    }
+    Ok(())
+}
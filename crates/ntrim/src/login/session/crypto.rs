@@ -0,0 +1,236 @@
+use std::sync::OnceLock;
+use aes_gcm::{Aes256Gcm, Key, KeyInit, Nonce};
+use aes_gcm::aead::{Aead, Payload};
+use argon2::{Algorithm, Argon2, Params, Version};
+use serde::{Deserialize, Serialize};
+use crate::config::SessionConfig;
+use crate::login::session::file::{hex_bytes, SessionFileError};
+
+/// 加密会话容器的标识
+pub const ENCRYPTED_SESSION_FORMAT: &str = "ntrim-encrypted-session";
+/// 口令通过环境变量传入，避免写入配置文件
+pub const SESSION_PASSPHRASE_ENV: &str = "NTRIM_SESSION_PASSPHRASE";
+
+const ARGON2_M_COST: u32 = 19 * 1024;
+const ARGON2_T_COST: u32 = 2;
+const ARGON2_P_COST: u32 = 1;
+/// 文件头中的参数不可信，超过上限时拒绝派生，避免构造的文件耗尽内存或CPU
+const ARGON2_MAX_M_COST: u32 = 1024 * 1024;
+const ARGON2_MAX_T_COST: u32 = 16;
+const ARGON2_MAX_P_COST: u32 = 16;
+
+static SESSION_CIPHER: OnceLock<Option<SessionCipher>> = OnceLock::new();
+
+/// 加密后的会话文件，明文为会话json
+#[derive(Debug, Serialize, Deserialize)]
+struct EncryptedSession {
+    format: String,
+    version: u32,
+    kdf: Kdf,
+    #[serde(with = "hex_bytes")]
+    nonce: Vec<u8>,
+    #[serde(with = "hex_bytes")]
+    ciphertext: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Kdf {
+    Argon2id {
+        #[serde(with = "hex_bytes")]
+        salt: Vec<u8>,
+        m_cost: u32,
+        t_cost: u32,
+        p_cost: u32,
+    },
+    KeyFile,
+}
+
+#[derive(Clone)]
+enum KeySource {
+    Passphrase(String),
+    KeyFile([u8; 32]),
+}
+
+/// 会话文件加解密使用的密钥
+#[derive(Clone)]
+pub struct SessionCipher {
+    source: KeySource,
+    /// 保存时使用的kdf与密钥，口令派生较慢，启动时派生一次
+    kdf: Kdf,
+    key: [u8; 32],
+}
+
+impl SessionCipher {
+    pub fn from_passphrase(passphrase: String) -> Result<Self, SessionFileError> {
+        let salt: [u8; 16] = rand::random();
+        let kdf = Kdf::Argon2id {
+            salt: salt.to_vec(),
+            m_cost: ARGON2_M_COST,
+            t_cost: ARGON2_T_COST,
+            p_cost: ARGON2_P_COST,
+        };
+        let key = derive_key(&passphrase, &kdf)?;
+        Ok(Self { source: KeySource::Passphrase(passphrase), kdf, key })
+    }
+
+    /// 密钥文件内容为32字节原始数据或64位十六进制字符串
+    pub fn from_key_file(path: &str) -> Result<Self, SessionFileError> {
+        let data = std::fs::read(path)?;
+        let key = match data.len() {
+            32 => data,
+            _ => hex::decode(String::from_utf8_lossy(&data).trim()).unwrap_or_default(),
+        };
+        let key: [u8; 32] = key.try_into().map_err(|_| SessionFileError::Crypto(
+            format!("key file {} must contain 32 raw bytes or 64 hex characters", path)
+        ))?;
+        Ok(Self { source: KeySource::KeyFile(key), kdf: Kdf::KeyFile, key })
+    }
+
+    /// 按配置创建，未配置密钥时返回None
+    pub fn from_config(config: &SessionConfig) -> Result<Option<Self>, SessionFileError> {
+        if let Some(key_file) = &config.key_file {
+            return Self::from_key_file(key_file).map(Some);
+        }
+        match std::env::var(SESSION_PASSPHRASE_ENV) {
+            Ok(passphrase) if !passphrase.is_empty() => Self::from_passphrase(passphrase).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    pub fn encrypt(&self, data: &str) -> Result<String, SessionFileError> {
+        let nonce: [u8; 12] = rand::random();
+        let ciphertext = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&self.key))
+            .encrypt(Nonce::from_slice(&nonce), Payload {
+                msg: data.as_bytes(),
+                aad: ENCRYPTED_SESSION_FORMAT.as_bytes(),
+            })
+            .map_err(|e| SessionFileError::Crypto(format!("failed to encrypt session: {}", e)))?;
+        let container = EncryptedSession {
+            format: ENCRYPTED_SESSION_FORMAT.to_string(),
+            version: 1,
+            kdf: self.kdf.clone(),
+            nonce: nonce.to_vec(),
+            ciphertext,
+        };
+        Ok(serde_json::to_string_pretty(&container)?)
+    }
+
+    fn decrypt(&self, container: EncryptedSession) -> Result<String, SessionFileError> {
+        let key = match (&container.kdf, &self.source) {
+            (Kdf::KeyFile, KeySource::KeyFile(key)) => *key,
+            (Kdf::Argon2id { .. }, KeySource::Passphrase(passphrase)) => derive_key(passphrase, &container.kdf)?,
+            (Kdf::KeyFile, _) => return Err(SessionFileError::Crypto("session file is encrypted with a key file".to_string())),
+            (Kdf::Argon2id { .. }, _) => return Err(SessionFileError::Crypto(
+                format!("session file is encrypted with a passphrase, set {}", SESSION_PASSPHRASE_ENV)
+            )),
+        };
+        if container.nonce.len() != 12 {
+            return Err(SessionFileError::invalid("nonce", "expected 12 bytes"));
+        }
+        let data = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key))
+            .decrypt(Nonce::from_slice(&container.nonce), Payload {
+                msg: container.ciphertext.as_slice(),
+                aad: ENCRYPTED_SESSION_FORMAT.as_bytes(),
+            })
+            .map_err(|_| SessionFileError::Crypto("wrong key or corrupted session file".to_string()))?;
+        String::from_utf8(data).map_err(|_| SessionFileError::Crypto("decrypted session is not utf-8".to_string()))
+    }
+}
+
+fn derive_key(passphrase: &str, kdf: &Kdf) -> Result<[u8; 32], SessionFileError> {
+    let Kdf::Argon2id { salt, m_cost, t_cost, p_cost } = kdf else {
+        return Err(SessionFileError::Crypto("key derivation requires argon2id".to_string()));
+    };
+    for (name, value, max) in [
+        ("m_cost", *m_cost, ARGON2_MAX_M_COST),
+        ("t_cost", *t_cost, ARGON2_MAX_T_COST),
+        ("p_cost", *p_cost, ARGON2_MAX_P_COST),
+    ] {
+        if value > max {
+            return Err(SessionFileError::invalid(format!("kdf.{}", name), format!("{} exceeds the maximum {}", value, max)));
+        }
+    }
+    let params = Params::new(*m_cost, *t_cost, *p_cost, Some(32))
+        .map_err(|e| SessionFileError::Crypto(format!("invalid argon2 params: {}", e)))?;
+    let mut key = [0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .map_err(|e| SessionFileError::Crypto(format!("failed to derive key: {}", e)))?;
+    Ok(key)
+}
+
+/// 启动时按配置初始化会话加密，`encrypt = true`却没有密钥时报错，避免明文落盘
+pub fn init_session_cipher(config: &SessionConfig) -> Result<(), SessionFileError> {
+    let cipher = SessionCipher::from_config(config)?;
+    if config.encrypt && cipher.is_none() {
+        return Err(SessionFileError::Crypto(format!(
+            "session encryption is required, please set session.key_file or {}", SESSION_PASSPHRASE_ENV
+        )));
+    }
+    if cipher.is_some() {
+        info!("Session files will be encrypted at rest");
+    }
+    let _ = SESSION_CIPHER.set(cipher);
+    Ok(())
+}
+
+pub fn session_cipher() -> Option<&'static SessionCipher> {
+    SESSION_CIPHER.get().and_then(|c| c.as_ref())
+}
+
+fn is_encrypted_value(value: &serde_json::Value) -> bool {
+    value.get("format").and_then(|f| f.as_str()) == Some(ENCRYPTED_SESSION_FORMAT)
+}
+
+/// 读取会话文件内容，加密的容器会被解密为会话json
+pub fn open(data: String, cipher: Option<&SessionCipher>) -> Result<String, SessionFileError> {
+    let value: serde_json::Value = serde_json::from_str(&data)?;
+    if !is_encrypted_value(&value) {
+        return Ok(data);
+    }
+    let container: EncryptedSession = serde_path_to_error::deserialize(value)
+        .map_err(|e| SessionFileError::invalid(e.path().to_string(), e.inner().to_string()))?;
+    if container.version != 1 {
+        return Err(SessionFileError::Crypto(format!("unsupported encrypted session version {}", container.version)));
+    }
+    let cipher = cipher.ok_or(SessionFileError::Crypto(format!(
+        "session file is encrypted, please set session.key_file or {}", SESSION_PASSPHRASE_ENV
+    )))?;
+    cipher.decrypt(container)
+}
+
+/// 配置了密钥时加密，否则原样保存
+pub fn seal(data: String, cipher: Option<&SessionCipher>) -> Result<String, SessionFileError> {
+    match cipher {
+        Some(cipher) => cipher.encrypt(&data),
+        None => Ok(data),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn passphrase_cipher() -> SessionCipher {
+        SessionCipher::from_passphrase("passphrase".to_string()).unwrap()
+    }
+
+    #[test]
+    fn seal_and_open() {
+        let cipher = passphrase_cipher();
+        let sealed = seal("{\"uin\":1}".to_string(), Some(&cipher)).unwrap();
+        assert!(!sealed.contains("uin"));
+        assert_eq!(open(sealed.clone(), Some(&cipher)).unwrap(), "{\"uin\":1}");
+        assert!(matches!(open(sealed, None), Err(SessionFileError::Crypto(_))));
+    }
+
+    #[test]
+    fn oversized_kdf_params_are_rejected() {
+        let cipher = passphrase_cipher();
+        let mut container: serde_json::Value = serde_json::from_str(&cipher.encrypt("{}").unwrap()).unwrap();
+        container["kdf"]["m_cost"] = serde_json::Value::from(u32::MAX);
+        let err = open(container.to_string(), Some(&cipher)).unwrap_err();
+        assert!(matches!(err, SessionFileError::InvalidField { path, .. } if path == "kdf.m_cost"));
+    }
+}
@@ -0,0 +1,570 @@
+use std::collections::BTreeMap;
+use std::io::Write;
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use thiserror::Error;
+use ntrim_core::session::device::Device;
+use ntrim_core::session::protocol::protocol;
+use ntrim_core::session::SsoSession;
+use ntrim_core::session::ticket::{SigType, Ticket, TicketManager};
+use ntrim_tools::crypto::qqtea::{qqtea_decrypt, qqtea_encrypt};
+use crate::login::session::crypto;
+use crate::login::session::crypto::{session_cipher, SessionCipher};
+use crate::login::session::register::rand_qimei;
+
+/// 当前会话文件版本
+/// - 0: `default.account.json`的布局，没有sigs
+/// - 1: 扁平布局，设备信息与账号信息混在一起，qimei每次载入随机生成
+/// - 2: 当前布局
+pub const SESSION_FILE_VERSION: u32 = 2;
+
+#[derive(Error, Debug)]
+pub enum SessionFileError {
+    #[error("Failed to access session file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Session file is not valid json: {0}")]
+    Json(#[from] serde_json::Error),
+    /// path为出错字段的路径，例如`sigs.en_a1`
+    #[error("Invalid field `{path}`: {message}")]
+    InvalidField {
+        path: String,
+        message: String,
+    },
+    #[error("Session file encryption error: {0}")]
+    Crypto(String),
+    #[error("Unsupported session file version {0}, the latest supported version is {SESSION_FILE_VERSION}")]
+    UnsupportedVersion(u64),
+}
+
+impl SessionFileError {
+    pub(crate) fn invalid(path: impl Into<String>, message: impl Into<String>) -> Self {
+        SessionFileError::InvalidField { path: path.into(), message: message.into() }
+    }
+}
+
+/// 持久化的会话文件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionFile {
+    pub version: u32,
+    pub uin: u64,
+    pub uid: String,
+    #[serde(with = "hex_array")]
+    pub ksid: [u8; 16],
+    #[serde(with = "hex_array")]
+    pub guid: [u8; 16],
+    pub device: DeviceFile,
+    pub sigs: SigsFile,
+    /// sig_type -> ticket
+    #[serde(default)]
+    pub tickets: BTreeMap<u32, TicketFile>,
+    #[serde(default)]
+    pub last_grp_msg_time: u64,
+    #[serde(default)]
+    pub last_c2c_msg_time: u64,
+    #[serde(default)]
+    pub skey: String,
+    /// tlv_t16d返回的key，不属于任何域名
+    #[serde(default)]
+    pub pskey: String,
+    /// domain -> web key
+    #[serde(default)]
+    pub pskeys: BTreeMap<String, WebKeyFile>,
+    #[serde(default)]
+    pub update_time: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeviceFile {
+    pub android_id: String,
+    pub qimei: String,
+    pub dev_name: String,
+    pub brand: String,
+    pub os_ver: String,
+    pub code: String,
+    pub os_name: String,
+    pub vendor_os_name: String,
+    #[serde(with = "hex_bytes")]
+    pub fingerprint: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SigsFile {
+    /// encrypt_a1 + tgtgt_key，tgtgt_key不是可见字符时整体使用guid加密
+    #[serde(with = "hex_bytes")]
+    pub en_a1: Vec<u8>,
+    #[serde(with = "hex_bytes")]
+    pub no_pic_sig: Vec<u8>,
+    #[serde(with = "hex_bytes")]
+    pub wt_session_ticket: Vec<u8>,
+    #[serde(with = "hex_bytes")]
+    pub wt_session_key: Vec<u8>,
+    pub wt_session_create_time: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TicketFile {
+    #[serde(with = "hex_bytes")]
+    pub sig: Vec<u8>,
+    #[serde(with = "hex_bytes")]
+    pub sig_key: Vec<u8>,
+    pub create_time: i64,
+    pub expire_time: i64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WebKeyFile {
+    pub pskey: String,
+    #[serde(default)]
+    pub pt4token: String,
+}
+
+impl SessionFile {
+    /// 读取会话文件，旧版本的布局会被迁移到当前版本
+    /// 加密的会话文件会使用启动时配置的密钥解密
+    pub fn read(path: &str) -> Result<Self, SessionFileError> {
+        Self::read_with(path, session_cipher())
+    }
+
+    pub fn read_with(path: &str, cipher: Option<&SessionCipher>) -> Result<Self, SessionFileError> {
+        let data = std::fs::read_to_string(path)?;
+        Self::from_json(&crypto::open(data, cipher)?)
+    }
+
+    pub fn from_json(data: &str) -> Result<Self, SessionFileError> {
+        let value: Value = serde_json::from_str(data)?;
+        Self::from_value(value)
+    }
+
+    pub fn from_value(value: Value) -> Result<Self, SessionFileError> {
+        let value = migrate(value)?;
+        serde_path_to_error::deserialize(value).map_err(|e| {
+            SessionFileError::invalid(e.path().to_string(), e.inner().to_string())
+        })
+    }
+
+    pub fn to_json(&self) -> Result<String, SessionFileError> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// 配置了会话加密时写入加密容器
+    pub fn write(&self, path: &str) -> Result<(), SessionFileError> {
+        self.write_with(path, session_cipher())
+    }
+
+    /// 先写入临时文件再重命名，进程中途退出也不会留下写了一半的会话文件
+    pub fn write_with(&self, path: &str, cipher: Option<&SessionCipher>) -> Result<(), SessionFileError> {
+        let data = crypto::seal(self.to_json()?, cipher)?;
+        let tmp_path = format!("{}.tmp", path);
+        let mut file = std::fs::File::create(&tmp_path)?;
+        file.write_all(data.as_bytes())?;
+        file.sync_all()?;
+        drop(file);
+        std::fs::rename(&tmp_path, path)?;
+        Ok(())
+    }
+
+    pub fn from_session(session: &SsoSession) -> Self {
+        let device = &session.device;
+        let mut en_a1 = [&session.encrypt_a1[..], &session.tgtgt_key[..]].concat();
+        if !is_valid_en_a1(&session.tgtgt_key) {
+            // 登录得到的gt_key不是可见字符，载入时会回退到guid解密
+            en_a1 = qqtea_encrypt(en_a1.as_slice(), &session.guid);
+        }
+        let tickets = session.tickets.iter()
+            .filter_map(|(id, t)| {
+                let sig = t.sig.clone().unwrap_or_default();
+                if t.sig.is_some() && sig.is_empty() { return None }
+                Some((id.bits(), TicketFile {
+                    sig,
+                    sig_key: t.sig_key.clone(),
+                    create_time: t.create_time,
+                    expire_time: t.expire_time,
+                }))
+            })
+            .collect();
+        let pskeys = session.pskeys.iter()
+            .map(|(domain, (pskey, pt4token))| (domain.clone(), WebKeyFile {
+                pskey: pskey.clone(),
+                pt4token: pt4token.clone(),
+            }))
+            .collect();
+        Self {
+            version: SESSION_FILE_VERSION,
+            uin: session.uin,
+            uid: session.uid.clone(),
+            ksid: session.ksid,
+            guid: session.guid,
+            device: DeviceFile {
+                android_id: device.android_id.clone(),
+                qimei: device.qimei.clone(),
+                dev_name: device.device_name.clone(),
+                brand: device.brand.clone(),
+                os_ver: device.os_ver.clone(),
+                code: device.code.clone(),
+                os_name: device.os_name.clone(),
+                vendor_os_name: device.vendor_os_name.clone(),
+                fingerprint: device.fingerprint.to_vec(),
+            },
+            sigs: SigsFile {
+                en_a1,
+                no_pic_sig: session.no_pic_sig.clone(),
+                wt_session_ticket: session.wt_session_ticket.clone(),
+                wt_session_key: session.wt_session_key.clone(),
+                wt_session_create_time: session.wt_session_create_time,
+            },
+            tickets,
+            last_grp_msg_time: session.last_grp_msg_time,
+            last_c2c_msg_time: session.last_c2c_msg_time,
+            skey: session.skey.clone(),
+            pskey: session.pskey.clone(),
+            pskeys,
+            update_time: Local::now().to_rfc3339(),
+        }
+    }
+
+    pub fn into_session(self) -> Result<SsoSession, SessionFileError> {
+        let mut android_id = self.device.android_id;
+        if android_id.len() > 16 {
+            warn!("Android id is too long, maybe it's not a valid android id: {}", android_id);
+            android_id.truncate(16);
+            warn!("Truncated android id to: {}", android_id)
+        } else if android_id.len() < 16 {
+            return Err(SessionFileError::invalid("device.android_id", format!("expected 16 characters, got {}", android_id.len())));
+        }
+        let qimei = if self.device.qimei.is_empty() { rand_qimei() } else { self.device.qimei };
+        let device = Device::new(
+            android_id,
+            qimei,
+            self.device.dev_name,
+            self.device.brand,
+            self.device.os_ver,
+            self.device.vendor_os_name,
+            self.device.fingerprint,
+            self.device.code,
+            self.device.os_name
+        );
+        let mut session = SsoSession::new(
+            (self.uin, self.uid),
+            protocol::qq_9_0_20().clone(),
+            device,
+            self.ksid,
+            self.guid
+        );
+
+        let mut a1_with_tgtgt_key = self.sigs.en_a1;
+        if !a1_with_tgtgt_key.is_empty() {
+            let tail_valid = a1_with_tgtgt_key.len() >= 16
+                && is_valid_en_a1(&a1_with_tgtgt_key[a1_with_tgtgt_key.len() - 16..]);
+            if !tail_valid {
+                warn!("Your A1 is invalid, try to decrypt with guid");
+                a1_with_tgtgt_key = qqtea_decrypt(a1_with_tgtgt_key.as_slice(), &self.guid)
+                    .ok_or(SessionFileError::invalid("sigs.en_a1", "failed to decrypt with guid"))?;
+            }
+            if a1_with_tgtgt_key.len() < 16 {
+                return Err(SessionFileError::invalid("sigs.en_a1", "too short to contain tgtgt_key"));
+            }
+            let split = a1_with_tgtgt_key.len() - 16;
+            session.tgtgt_key = a1_with_tgtgt_key.split_off(split);
+            session.encrypt_a1 = a1_with_tgtgt_key;
+        }
+        session.no_pic_sig = self.sigs.no_pic_sig;
+        session.wt_session_ticket = self.sigs.wt_session_ticket;
+        session.wt_session_key = self.sigs.wt_session_key;
+        session.wt_session_create_time = self.sigs.wt_session_create_time;
+        session.last_grp_msg_time = self.last_grp_msg_time;
+        session.last_c2c_msg_time = self.last_c2c_msg_time;
+        session.skey = self.skey;
+        session.pskey = self.pskey;
+        for (domain, key) in self.pskeys {
+            if key.pskey.is_empty() { continue }
+            session.pskeys.insert(domain, (key.pskey, key.pt4token));
+        }
+
+        for (sig_type, t) in self.tickets {
+            let id = SigType::from_bits(sig_type)
+                .ok_or(SessionFileError::invalid(format!("tickets.{}", sig_type), "unknown sig type"))?;
+            session.insert(Ticket {
+                id,
+                sig_key: t.sig_key,
+                sig: Some(t.sig),
+                create_time: t.create_time,
+                expire_time: t.expire_time,
+            });
+        }
+        Ok(session)
+    }
+}
+
+fn is_valid_en_a1(en_a1: &[u8]) -> bool {
+    en_a1.iter().all(|&x| x >= 33 && x <= 126)
+}
+
+/// 将旧版本布局逐级迁移到当前版本
+fn migrate(mut value: Value) -> Result<Value, SessionFileError> {
+    let data = value.as_object_mut()
+        .ok_or(SessionFileError::invalid("$", "expected a json object"))?;
+    let mut version = match data.get("version") {
+        Some(version) => version.as_u64()
+            .ok_or(SessionFileError::invalid("version", "expected an unsigned integer"))?,
+        None if data.contains_key("sigs") => 1,
+        None => 0,
+    };
+    if version > SESSION_FILE_VERSION as u64 {
+        return Err(SessionFileError::UnsupportedVersion(version));
+    }
+    if version < SESSION_FILE_VERSION as u64 {
+        info!("Migrating session file from version {} to {}", version, SESSION_FILE_VERSION);
+    }
+    while version < SESSION_FILE_VERSION as u64 {
+        match version {
+            0 => migrate_v0(data),
+            1 => migrate_v1(data)?,
+            _ => unreachable!(),
+        }
+        version += 1;
+    }
+    data.insert("version".to_string(), Value::from(SESSION_FILE_VERSION));
+    Ok(value)
+}
+
+/// `default.account.json`没有登录凭证
+fn migrate_v0(data: &mut Map<String, Value>) {
+    let mut sigs = Map::new();
+    sigs.insert("en_a1".to_string(), Value::from(""));
+    sigs.insert("no_pic_sig".to_string(), Value::from(""));
+    sigs.insert("wt_session_ticket".to_string(), Value::from(""));
+    sigs.insert("wt_session_key".to_string(), Value::from(""));
+    sigs.insert("wt_session_create_time".to_string(), Value::from(0));
+    data.insert("sigs".to_string(), Value::Object(sigs));
+}
+
+/// 设备信息移入device，uin改为数字，ticket字段改为下划线命名，补充qimei
+fn migrate_v1(data: &mut Map<String, Value>) -> Result<(), SessionFileError> {
+    if let Some(Value::String(uin)) = data.get("uin") {
+        let uin: u64 = uin.parse()
+            .map_err(|_| SessionFileError::invalid("uin", format!("`{}` is not a number", uin)))?;
+        data.insert("uin".to_string(), Value::from(uin));
+    }
+
+    let mut device = Map::new();
+    for key in ["android_id", "dev_name", "brand", "os_ver", "code", "os_name", "vendor_os_name", "fingerprint"] {
+        if let Some(v) = data.remove(key) {
+            device.insert(key.to_string(), v);
+        }
+    }
+    device.insert("qimei".to_string(), Value::from(rand_qimei()));
+    data.insert("device".to_string(), Value::Object(device));
+
+    if let Some(ticket) = data.remove("ticket") {
+        let Value::Object(ticket) = ticket else {
+            return Err(SessionFileError::invalid("ticket", "expected a json object"));
+        };
+        let mut tickets = Map::new();
+        for (sig_type, t) in ticket {
+            let Value::Object(mut t) = t else {
+                return Err(SessionFileError::invalid(format!("ticket.{}", sig_type), "expected a json object"));
+            };
+            // 旧版模板中存在空的占位ticket
+            if t.get("sig").and_then(|v| v.as_str()).is_some_and(|sig| sig.is_empty()) {
+                continue;
+            }
+            for (old, new) in [("sigKey", "sig_key"), ("createTime", "create_time"), ("expireTime", "expire_time")] {
+                if let Some(v) = t.remove(old) {
+                    t.insert(new.to_string(), v);
+                }
+            }
+            tickets.insert(sig_type, Value::Object(t));
+        }
+        data.insert("tickets".to_string(), Value::Object(tickets));
+    }
+    Ok(())
+}
+
+pub(crate) mod hex_bytes {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use serde::de::Error;
+
+    pub fn serialize<S: Serializer>(data: &Vec<u8>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(data))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u8>, D::Error> {
+        let data = String::deserialize(deserializer)?;
+        hex::decode(&data).map_err(|e| D::Error::custom(format!("invalid hex string: {}", e)))
+    }
+}
+
+mod hex_array {
+    use serde::{Deserializer, Serializer};
+    use serde::de::Error;
+
+    pub fn serialize<S: Serializer, const N: usize>(data: &[u8; N], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(data))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, const N: usize>(deserializer: D) -> Result<[u8; N], D::Error> {
+        let data = super::hex_bytes::deserialize(deserializer)?;
+        let len = data.len();
+        data.try_into().map_err(|_| D::Error::custom(format!("expected {} bytes, got {}", N, len)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `default.account.json`布局
+    const V0_DOCUMENT: &str = r#"{
+        "uid": "u_test",
+        "ksid": "000102030405060708090a0b0c0d0e0f",
+        "guid": "0f0e0d0c0b0a09080706050403020100",
+        "uin": "12345678",
+        "android_id": "0123456789abcdef0123",
+        "dev_name": "LGE-AN10",
+        "os_ver": "12",
+        "fingerprint": "0a93",
+        "vendor_os_name": "NTRNM Project",
+        "brand": "Xiaomi",
+        "os_name": "MIUI",
+        "code": "elish",
+        "ticket": {
+            "2097152": { "sig": "", "sigKey": "", "createTime": 0, "expireTime": 0 }
+        }
+    }"#;
+
+    /// 扁平布局，en_a1为encrypt_a1(0102) + tgtgt_key("aaaaaaaaaaaaaaaa")
+    const V1_DOCUMENT: &str = r#"{
+        "uid": "u_test",
+        "ksid": "000102030405060708090a0b0c0d0e0f",
+        "guid": "0f0e0d0c0b0a09080706050403020100",
+        "uin": "12345678",
+        "android_id": "0123456789abcdef",
+        "dev_name": "LGE-AN10",
+        "os_ver": "12",
+        "fingerprint": "0a93",
+        "vendor_os_name": "NTRNM Project",
+        "brand": "Xiaomi",
+        "os_name": "MIUI",
+        "code": "elish",
+        "sigs": {
+            "en_a1": "010261616161616161616161616161616161",
+            "no_pic_sig": "aabb",
+            "wt_session_ticket": "ccdd",
+            "wt_session_key": "eeff",
+            "wt_session_create_time": 1700000000
+        },
+        "ticket": {
+            "262144": { "sig": "d2d2", "sigKey": "4b4b", "createTime": 1700000000, "expireTime": 1800000000 }
+        },
+        "skey": "@abcdefgh"
+    }"#;
+
+    #[test]
+    fn migrate_v0_document() {
+        let file = SessionFile::from_json(V0_DOCUMENT).unwrap();
+        assert_eq!(file.version, SESSION_FILE_VERSION);
+        assert_eq!(file.uin, 12345678);
+        assert_eq!(file.device.dev_name, "LGE-AN10");
+        assert!(!file.device.qimei.is_empty());
+        // 空的占位ticket会被丢弃
+        assert!(file.tickets.is_empty());
+        assert!(file.sigs.en_a1.is_empty());
+
+        let session = file.into_session().unwrap();
+        assert_eq!(session.uin, 12345678);
+        assert_eq!(session.device.android_id, "0123456789abcdef");
+        assert!(!session.is_login());
+    }
+
+    #[test]
+    fn migrate_v1_document() {
+        let file = SessionFile::from_json(V1_DOCUMENT).unwrap();
+        assert_eq!(file.version, SESSION_FILE_VERSION);
+        let ticket = &file.tickets[&SigType::D2.bits()];
+        assert_eq!(ticket.sig, vec![0xd2, 0xd2]);
+        assert_eq!(ticket.sig_key, vec![0x4b, 0x4b]);
+        assert_eq!(ticket.expire_time, 1800000000);
+
+        let session = file.into_session().unwrap();
+        assert_eq!(session.encrypt_a1, vec![0x01, 0x02]);
+        assert_eq!(session.tgtgt_key, b"aaaaaaaaaaaaaaaa".to_vec());
+        assert_eq!(session.no_pic_sig, vec![0xaa, 0xbb]);
+        assert_eq!(session.wt_session_create_time, 1700000000);
+        assert_eq!(session.skey, "@abcdefgh");
+        assert_eq!(session.ticket(SigType::D2).unwrap().sig, Some(vec![0xd2, 0xd2]));
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut session = SessionFile::from_json(V1_DOCUMENT).unwrap().into_session().unwrap();
+        session.pskey = "superkey".to_string();
+        session.pskeys.insert("qun.qq.com".to_string(), ("pskey".to_string(), "pt4token".to_string()));
+
+        let json = SessionFile::from_session(&session).to_json().unwrap();
+        let file = SessionFile::from_json(&json).unwrap();
+        assert_eq!(file.to_json().unwrap(), json);
+
+        let loaded = file.into_session().unwrap();
+        assert_eq!(loaded.uin, session.uin);
+        assert_eq!(loaded.uid, session.uid);
+        assert_eq!(loaded.ksid, session.ksid);
+        assert_eq!(loaded.guid, session.guid);
+        assert_eq!(loaded.device.qimei, session.device.qimei);
+        assert_eq!(loaded.encrypt_a1, session.encrypt_a1);
+        assert_eq!(loaded.tgtgt_key, session.tgtgt_key);
+        assert_eq!(loaded.wt_session_key, session.wt_session_key);
+        assert_eq!(loaded.skey, session.skey);
+        assert_eq!(loaded.pskey, "superkey");
+        assert_eq!(loaded.web_key("h5.qun.qq.com"), Some(&("pskey".to_string(), "pt4token".to_string())));
+        let ticket = loaded.ticket(SigType::D2).unwrap();
+        assert_eq!(ticket.sig, Some(vec![0xd2, 0xd2]));
+        assert_eq!(ticket.sig_key, vec![0x4b, 0x4b]);
+    }
+
+    #[test]
+    fn round_trip_non_printable_tgtgt_key() {
+        let mut session = SessionFile::from_json(V1_DOCUMENT).unwrap().into_session().unwrap();
+        session.tgtgt_key = vec![0u8; 16];
+        let json = SessionFile::from_session(&session).to_json().unwrap();
+        let loaded = SessionFile::from_json(&json).unwrap().into_session().unwrap();
+        assert_eq!(loaded.encrypt_a1, session.encrypt_a1);
+        assert_eq!(loaded.tgtgt_key, vec![0u8; 16]);
+    }
+
+    #[test]
+    fn invalid_field_reports_path() {
+        let mut value: Value = serde_json::from_str(V1_DOCUMENT).unwrap();
+        value["sigs"]["en_a1"] = Value::from("not hex");
+        let err = SessionFile::from_value(value).unwrap_err();
+        let SessionFileError::InvalidField { path, message } = err else {
+            panic!("unexpected error: {}", err);
+        };
+        assert_eq!(path, "sigs.en_a1");
+        assert!(message.contains("invalid hex string"), "{}", message);
+
+        let mut value: Value = serde_json::from_str(V1_DOCUMENT).unwrap();
+        value["uin"] = Value::from("not a number");
+        assert!(matches!(
+            SessionFile::from_value(value),
+            Err(SessionFileError::InvalidField { path, .. }) if path == "uin"
+        ));
+
+        let mut value: Value = serde_json::from_str(V1_DOCUMENT).unwrap();
+        value["ksid"] = Value::from("0102");
+        assert!(matches!(
+            SessionFile::from_value(value),
+            Err(SessionFileError::InvalidField { path, .. }) if path == "ksid"
+        ));
+    }
+
+    #[test]
+    fn newer_version_is_rejected() {
+        let mut value: Value = serde_json::from_str(V1_DOCUMENT).unwrap();
+        value["version"] = Value::from(SESSION_FILE_VERSION + 1);
+        assert!(matches!(
+            SessionFile::from_value(value),
+            Err(SessionFileError::UnsupportedVersion(v)) if v == SESSION_FILE_VERSION as u64 + 1
+        ));
+    }
+}
@@ -0,0 +1,320 @@
+use bytes::{Buf, Bytes};
+use chrono::Local;
+use serde::Deserialize;
+use serde_json::Value;
+use ntrim_core::session::device::Device;
+use ntrim_core::session::protocol::protocol;
+use ntrim_core::session::SsoSession;
+use ntrim_core::session::ticket::{SigType, Ticket, TicketManager};
+use crate::login::session::file::{SessionFile, SessionFileError};
+use crate::login::session::register::rand_qimei;
+
+/// 从Shamrock导出的会话导入
+///
+/// 导出文件与`default.account.json`同为扁平布局：账号、设备信息与`ticket`表，
+/// 登录凭证位于`sigs`中，按旧版本会话文件迁移即可
+pub fn import_shamrock(path: &str) -> Result<SsoSession, SessionFileError> {
+    parse_shamrock(&std::fs::read_to_string(path)?)
+}
+
+fn parse_shamrock(data: &str) -> Result<SsoSession, SessionFileError> {
+    let value: Value = serde_json::from_str(data)?;
+    if value.get("version").is_some() {
+        return Err(SessionFileError::invalid("version", "already a ntrim session file, no need to import"));
+    }
+    SessionFile::from_value(value)?.into_session()
+}
+
+/// go-cqhttp/ricq的device.json，两者字段基本一致
+#[derive(Debug, Deserialize)]
+struct ForeignDevice {
+    model: String,
+    device: String,
+    brand: String,
+    finger_print: String,
+    android_id: String,
+    mac_address: String,
+    #[serde(default)]
+    vendor_name: String,
+    #[serde(default)]
+    os_type: String,
+    version: ForeignVersion,
+}
+
+#[derive(Debug, Deserialize)]
+struct ForeignVersion {
+    release: String,
+}
+
+/// 登录凭证，go-cqhttp为二进制的session.token，ricq为json
+#[derive(Debug, Default, Deserialize)]
+struct ForeignToken {
+    uin: u64,
+    d2: Vec<u8>,
+    d2key: Vec<u8>,
+    tgt: Vec<u8>,
+    srm_token: Vec<u8>,
+    t133: Vec<u8>,
+    encrypted_a1: Vec<u8>,
+    wt_session_ticket_key: Vec<u8>,
+    tgtgt_key: Vec<u8>,
+}
+
+/// 从go-cqhttp的device.json与session.token导入
+pub fn import_gocqhttp(device_path: &str, token_path: &str) -> Result<SsoSession, SessionFileError> {
+    let device = parse_device(&std::fs::read_to_string(device_path)?)?;
+    let token = parse_gocqhttp_token(std::fs::read(token_path)?)?;
+    Ok(build_session(device, token))
+}
+
+/// 从ricq的device.json与token.json导入
+pub fn import_ricq(device_path: &str, token_path: &str) -> Result<SsoSession, SessionFileError> {
+    let device = parse_device(&std::fs::read_to_string(device_path)?)?;
+    let token = parse_json(&std::fs::read_to_string(token_path)?)?;
+    Ok(build_session(device, token))
+}
+
+fn parse_device(data: &str) -> Result<ForeignDevice, SessionFileError> {
+    parse_json(data)
+}
+
+fn parse_json<T: serde::de::DeserializeOwned>(data: &str) -> Result<T, SessionFileError> {
+    serde_path_to_error::deserialize(&mut serde_json::Deserializer::from_str(data))
+        .map_err(|e| SessionFileError::invalid(e.path().to_string(), e.inner().to_string()))
+}
+
+/// MiraiGo `GenToken`：uin(8) d2 d2key tgt srm_token t133 encrypted_a1 wt_session_ticket_key out_packet_session_id tgtgt_key
+/// 除uin外均为u16长度前缀
+fn parse_gocqhttp_token(data: Vec<u8>) -> Result<ForeignToken, SessionFileError> {
+    let mut buf = Bytes::from(data);
+    if buf.remaining() < 8 {
+        return Err(SessionFileError::invalid("uin", "token is too short"));
+    }
+    let uin = buf.get_u64();
+    let mut read = |field: &str| -> Result<Vec<u8>, SessionFileError> {
+        if buf.remaining() < 2 {
+            return Err(SessionFileError::invalid(field, "unexpected end of token"));
+        }
+        let len = buf.get_u16() as usize;
+        if buf.remaining() < len {
+            return Err(SessionFileError::invalid(field, "unexpected end of token"));
+        }
+        Ok(buf.copy_to_bytes(len).to_vec())
+    };
+    let d2 = read("d2")?;
+    let d2key = read("d2key")?;
+    let tgt = read("tgt")?;
+    let srm_token = read("srm_token")?;
+    let t133 = read("t133")?;
+    let encrypted_a1 = read("encrypted_a1")?;
+    let wt_session_ticket_key = read("wt_session_ticket_key")?;
+    let _out_packet_session_id = read("out_packet_session_id")?;
+    let tgtgt_key = read("tgtgt_key")?;
+    Ok(ForeignToken { uin, d2, d2key, tgt, srm_token, t133, encrypted_a1, wt_session_ticket_key, tgtgt_key })
+}
+
+fn build_session(device: ForeignDevice, token: ForeignToken) -> SsoSession {
+    // 与MiraiGo相同，guid = md5(android_id + mac_address)
+    let guid = md5::compute([device.android_id.as_bytes(), device.mac_address.as_bytes()].concat()).0;
+    let android_id = if device.android_id.len() == 16 && device.android_id.chars().all(|c| c.is_ascii_hexdigit()) {
+        device.android_id.clone()
+    } else {
+        let android_id = hex::encode(md5::compute(device.android_id.as_bytes()).0)[..16].to_string();
+        warn!("Android id {} is not 16 hex characters, using {} instead", device.android_id, android_id);
+        android_id
+    };
+    let os_name = if device.os_type.is_empty() { "android".to_string() } else { device.os_type };
+    let dev = Device::new(
+        android_id,
+        rand_qimei(),
+        device.model,
+        device.brand,
+        device.version.release,
+        device.vendor_name,
+        device.finger_print.into_bytes(),
+        device.device,
+        os_name
+    );
+    let mut session = SsoSession::new(
+        (token.uin, String::new()),
+        protocol::qq_9_0_20().clone(),
+        dev,
+        rand::random(),
+        guid
+    );
+    warn!("Uid of {} is unknown, it will be filled after the next st refresh", token.uin);
+
+    session.encrypt_a1 = token.encrypted_a1;
+    session.tgtgt_key = token.tgtgt_key;
+    session.no_pic_sig = token.srm_token;
+    session.wt_session_ticket = token.t133;
+    session.wt_session_key = token.wt_session_ticket_key;
+    // 不知道WT会话的创建时间，置0使下次刷新走RefreshSt
+    session.wt_session_create_time = 0;
+
+    // 过期时间未知，设为导入时间以便上线后立即刷新
+    let now = Local::now().timestamp();
+    if !token.d2.is_empty() {
+        session.insert(Ticket { id: SigType::D2, sig_key: token.d2key, sig: Some(token.d2), create_time: now, expire_time: now });
+    }
+    if !token.tgt.is_empty() {
+        session.insert(Ticket { id: SigType::A2, sig_key: Vec::new(), sig: Some(token.tgt), create_time: now, expire_time: now });
+    }
+    session
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEVICE: &str = r#"{
+        "display": "GMC.123456.001",
+        "product": "mirai",
+        "device": "mirai",
+        "board": "mirai",
+        "model": "mirai",
+        "finger_print": "mamoe/mirai/mirai:10/MIRAI.200122.001/1234567:user/release-keys",
+        "boot_id": "cb886ae2-00b6-4d68-a230-787f111d12c7",
+        "proc_version": "Linux version 3.0.31-cb886ae2",
+        "imei": "468356291846738",
+        "brand": "mamoe",
+        "bootloader": "unknown",
+        "base_band": "",
+        "version": { "incremental": "5891938", "release": "10", "codename": "REL", "sdk": 29 },
+        "sim_info": "T-Mobile",
+        "os_type": "android",
+        "mac_address": "00:50:56:C0:00:08",
+        "ip_address": [10, 0, 1, 3],
+        "wifi_bssid": "00:50:56:C0:00:08",
+        "wifi_ssid": "<unknown ssid>",
+        "imsi_md5": [],
+        "android_id": "MIRAI.123456.001",
+        "apn": "wifi",
+        "vendor_name": "MIUI",
+        "vendor_os_name": "mirai"
+    }"#;
+
+    fn put(buf: &mut Vec<u8>, data: &[u8]) {
+        buf.extend((data.len() as u16).to_be_bytes());
+        buf.extend(data);
+    }
+
+    /// 按GenToken的顺序拼出session.token
+    fn gocqhttp_token() -> Vec<u8> {
+        let mut buf = 12345678u64.to_be_bytes().to_vec();
+        for field in [
+            &b"d2"[..], b"d2key", b"tgt", b"srm", b"t133", b"a1", b"wtkey", b"out", b"tgtgtkeytgtgtkey"
+        ] {
+            put(&mut buf, field);
+        }
+        buf
+    }
+
+    #[test]
+    fn import_shamrock_sample() {
+        let session = parse_shamrock(r#"{
+            "uid": "u_test",
+            "ksid": "000102030405060708090a0b0c0d0e0f",
+            "guid": "0f0e0d0c0b0a09080706050403020100",
+            "uin": "12345678",
+            "android_id": "0123456789abcdef",
+            "dev_name": "LGE-AN10",
+            "os_ver": "12",
+            "fingerprint": "0a93",
+            "vendor_os_name": "Shamrock",
+            "brand": "Xiaomi",
+            "os_name": "MIUI",
+            "code": "elish",
+            "sigs": {
+                "en_a1": "010261616161616161616161616161616161",
+                "no_pic_sig": "",
+                "wt_session_ticket": "",
+                "wt_session_key": "",
+                "wt_session_create_time": 0
+            },
+            "ticket": {
+                "262144": { "sig": "d2d2", "sigKey": "4b4b", "createTime": 0, "expireTime": 0 }
+            }
+        }"#).unwrap();
+        assert_eq!(session.uin, 12345678);
+        assert_eq!(session.uid, "u_test");
+        assert_eq!(session.tgtgt_key, b"aaaaaaaaaaaaaaaa".to_vec());
+        assert_eq!(session.ticket(SigType::D2).unwrap().sig, Some(vec![0xd2, 0xd2]));
+    }
+
+    #[test]
+    fn shamrock_rejects_ntrim_session() {
+        let err = parse_shamrock(r#"{"version": 2}"#).unwrap_err();
+        assert!(matches!(err, SessionFileError::InvalidField { path, .. } if path == "version"));
+        assert!(matches!(parse_shamrock("{"), Err(SessionFileError::Json(_))));
+    }
+
+    #[test]
+    fn import_gocqhttp_sample() {
+        let token = parse_gocqhttp_token(gocqhttp_token()).unwrap();
+        let session = build_session(parse_device(DEVICE).unwrap(), token);
+        assert_eq!(session.uin, 12345678);
+        assert_eq!(session.encrypt_a1, b"a1".to_vec());
+        assert_eq!(session.tgtgt_key, b"tgtgtkeytgtgtkey".to_vec());
+        assert_eq!(session.no_pic_sig, b"srm".to_vec());
+        assert_eq!(session.wt_session_ticket, b"t133".to_vec());
+        assert_eq!(session.wt_session_key, b"wtkey".to_vec());
+        let d2 = session.ticket(SigType::D2).unwrap();
+        assert_eq!(d2.sig, Some(b"d2".to_vec()));
+        assert_eq!(d2.sig_key, b"d2key".to_vec());
+        assert_eq!(session.ticket(SigType::A2).unwrap().sig, Some(b"tgt".to_vec()));
+        // android_id不是16位十六进制时使用其md5
+        assert_eq!(session.device.android_id.len(), 16);
+        assert!(session.device.android_id.chars().all(|c| c.is_ascii_hexdigit()));
+        let guid = md5::compute(b"MIRAI.123456.00100:50:56:C0:00:08").0;
+        assert_eq!(session.guid, guid);
+    }
+
+    #[test]
+    fn truncated_gocqhttp_token() {
+        let token = gocqhttp_token();
+        // 每个截断位置都应返回错误而不是panic
+        for len in 0..token.len() {
+            assert!(parse_gocqhttp_token(token[..len].to_vec()).is_err(), "len {}", len);
+        }
+        let err = parse_gocqhttp_token(token[..4].to_vec()).unwrap_err();
+        assert!(matches!(err, SessionFileError::InvalidField { path, .. } if path == "uin"));
+        // 长度前缀超出剩余数据
+        let mut token = 1u64.to_be_bytes().to_vec();
+        token.extend(u16::MAX.to_be_bytes());
+        token.extend(b"d2");
+        let err = parse_gocqhttp_token(token).unwrap_err();
+        assert!(matches!(err, SessionFileError::InvalidField { path, .. } if path == "d2"));
+    }
+
+    #[test]
+    fn import_ricq_sample() {
+        let token: ForeignToken = parse_json(r#"{
+            "uin": 12345678,
+            "d2": [1, 2],
+            "d2key": [3, 4],
+            "tgt": [5],
+            "srm_token": [],
+            "t133": [],
+            "encrypted_a1": [6],
+            "out_packet_session_id": [],
+            "tgtgt_key": [7, 8],
+            "wt_session_ticket_key": []
+        }"#).unwrap();
+        let session = build_session(parse_device(DEVICE).unwrap(), token);
+        assert_eq!(session.uin, 12345678);
+        assert_eq!(session.encrypt_a1, vec![6]);
+        assert_eq!(session.tgtgt_key, vec![7, 8]);
+        assert_eq!(session.ticket(SigType::D2).unwrap().sig, Some(vec![1, 2]));
+    }
+
+    #[test]
+    fn malformed_ricq_token() {
+        let err = parse_json::<ForeignToken>(r#"{"uin": 12345678, "d2": [1, 2"#).unwrap_err();
+        assert!(matches!(err, SessionFileError::InvalidField { .. }));
+        let err = parse_json::<ForeignToken>(r#"{"uin": 12345678, "d2": "0102"}"#).unwrap_err();
+        assert!(matches!(err, SessionFileError::InvalidField { path, .. } if path == "d2"));
+        let err = parse_device(r#"{"model": "mirai"}"#).unwrap_err();
+        assert!(matches!(err, SessionFileError::InvalidField { .. }));
+    }
+}
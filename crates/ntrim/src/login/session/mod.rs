@@ -16,7 +16,12 @@ use crate::config::Config;
 use crate::login::session::register::save_session;
 use crate::qqsecurity::QSecurityViaHTTP;
 
-mod register;
+pub(crate) mod register;
+pub(crate) mod file;
+pub(crate) mod crypto;
+pub(crate) mod action;
+pub(crate) mod persist;
+pub(crate) mod import;
 
 pub async fn token_login(session_path: String, config: &Config) -> (Arc<Bot>, Receiver<WtloginResponse>) {
     let session = register::load_session(&session_path);
@@ -26,6 +31,7 @@ pub async fn token_login(session_path: String, config: &Config) -> (Arc<Bot>, Re
         error!("Failed to create bot session instance: {}", e)
     }).unwrap();
     let result_bot = bot.clone();
+    let save_interval = config.session.save_interval;
 
     let (mut tx, rx) = mpsc::channel(1);
     tokio::spawn(async move {
@@ -51,6 +57,8 @@ pub async fn token_login(session_path: String, config: &Config) -> (Arc<Bot>, Re
 
                         info!("RichMedia DownloadRKey: {:?}", service::rich_media::get_download_reky(&bot, 10).await);
 
+                        persist::spawn_session_persistence(bot.clone(), session_path.clone(), save_interval);
+
                         // 注册退出信号监听器 自动保存会话上下文
                         ntrim_tools::sigint::global_sigint_handler().add_listener(Pin::from(Box::new(async move {
                             info!("Received SIGINT, saving session and exiting");
@@ -0,0 +1,30 @@
+use std::sync::Arc;
+use tokio::time::Duration;
+use ntrim_core::bot::Bot;
+use crate::login::session::file::SessionFile;
+
+/// 一次登录响应会连续写入多个票据，稍等片刻合并为一次保存
+const SAVE_DEBOUNCE: Duration = Duration::from_secs(1);
+
+/// 会话持久化服务，凭证变化(写入票据、刷新成功)后以及定时保存会话
+pub fn spawn_session_persistence(bot: Arc<Bot>, session_path: String, save_interval: u64) {
+    tokio::spawn(async move {
+        let changed = bot.client.session.read().await.changed.clone();
+        let interval = Duration::from_secs(save_interval.max(1));
+        info!("Session will be saved to {} every {} seconds and whenever tickets change", session_path, interval.as_secs());
+        loop {
+            tokio::select! {
+                _ = changed.notified() => tokio::time::sleep(SAVE_DEBOUNCE).await,
+                _ = tokio::time::sleep(interval) => {}
+            }
+            // 只在锁内拍快照，加密与落盘放到阻塞线程，不阻塞会话锁与运行时
+            let file = SessionFile::from_session(&*bot.client.session.read().await);
+            let path = session_path.clone();
+            match tokio::task::spawn_blocking(move || file.write(&path)).await {
+                Ok(Ok(_)) => debug!("Session saved to {}", session_path),
+                Ok(Err(e)) => error!("Failed to save session to {}: {}", session_path, e),
+                Err(e) => error!("Session save task failed: {}", e),
+            }
+        }
+    });
+}
@@ -17,6 +17,11 @@ use ntrim_core::events::wtlogin_event::WtloginResponse;
 use ntrim_core::session::SsoSession;
 use ntrim_tools::sigint;
 use crate::args::{Args, LoginMode};
+use crate::login::password::password_login;
+use crate::login::phone::phone_login;
+use crate::login::qrlogin::qrcode_login;
+use crate::login::session::action::run_session_action;
+use crate::login::session::crypto::init_session_cipher;
 use crate::login::session::token_login;
 use crate::qqsecurity::QSecurityViaHTTP;
 
@@ -48,6 +53,17 @@ async fn main() {
             .expect("Configuration file parsing failure")
     };
 
+    if let Err(e) = init_session_cipher(&config.session) {
+        error!("Failed to initialize session encryption: {}", e);
+        return;
+    }
+    if let LoginMode::Session { action: Some(action), .. } = args.login_mode {
+        if let Err(e) = run_session_action(action) {
+            error!("Session action failed: {}", e);
+        }
+        return;
+    }
+
     #[cfg(feature = "sql")]
     {
         ntrim_core::initialize_pool(&config.sql.address).await;
@@ -55,11 +71,18 @@ async fn main() {
     }
 
     let ((bot, mut result), immediate_refresh) = match args.login_mode {
-        LoginMode::Password { qq, password } => {
-            panic!("Password login is not supported yet")
+        LoginMode::Password { qq, password, session_path } => {
+            (password_login(qq, password, session_path, &config).await, None)
+        }
+        LoginMode::Phone { phone, session_path } => {
+            (phone_login(phone, session_path, &config).await, None)
         }
-        LoginMode::Session { session_path, immediate_refresh } => {
-            (token_login(session_path, &config).await, immediate_refresh)
+        LoginMode::QrLogin { session_path, qrcode_path } => {
+            (qrcode_login(session_path, qrcode_path, &config).await, None)
+        }
+        LoginMode::Session { session_path, immediate_refresh, .. } => {
+            // 没有子命令时clap保证session_path存在
+            (token_login(session_path.unwrap(), &config).await, immediate_refresh)
         }
     };
 
@@ -73,18 +96,24 @@ async fn main() {
                 error!("Login failed: {}", e);
                 return;
             }
-            WtloginResponse::RefreshSigSuccess => panic!("RefreshSigSuccess is not supported yet") // 首次进入程序不该有这个分支
+            WtloginResponse::RefreshSigSuccess => panic!("RefreshSigSuccess is not supported yet"), // 首次进入程序不该有这个分支
+            resp => {
+                // 登录验证已在各登录方式中处理完毕
+                error!("Login failed, unexpected response: {:?}", resp);
+                return;
+            }
         };
     }
 
     // Here we can start the backend because the bot is online
     if immediate_refresh.map_or_else(|| false, |v| v) {
-        ntrim_core::refresh_session::refresh_sig(&bot).await;
+        ntrim_core::refresh_session::refresh_tickets(&bot).await;
     }
 
     if cfg!(feature = "onebot") {
         info!("Using OneBot backend, see https://github.com/botuniverse/onebot");
-
+        #[cfg(feature = "onebot")]
+        backend::onebot::start(bot.clone(), &config.onebot);
     } else if cfg!(feature = "kritor") {
         info!("Using Kritor backend, see https://github.com/KarinJS/kritor");
 